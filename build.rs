@@ -21,8 +21,8 @@ fn main() {
     }
 
     if let Some(rustc) = rustc_version() {
-        if rustc.minor < 48 {
-            println!("cargo:warning=The cxx crate requires a rustc version 1.48.0 or newer.");
+        if rustc.minor < 51 {
+            println!("cargo:warning=The cxx crate requires a rustc version 1.51.0 or newer.");
             println!(
                 "cargo:warning=You appear to be building with: {}",
                 rustc.version,
@@ -14,6 +14,11 @@ void cxxbridge1$cxx_string$init(std::string *s, const std::uint8_t *ptr,
   new (s) std::string(reinterpret_cast<const char *>(ptr), len);
 }
 
+std::string *cxxbridge1$cxx_string$new(const std::uint8_t *ptr,
+                                       std::size_t len) noexcept {
+  return new std::string(reinterpret_cast<const char *>(ptr), len);
+}
+
 void cxxbridge1$cxx_string$destroy(std::string *s) noexcept {
   using std::string;
   s->~string();
@@ -29,11 +34,19 @@ std::size_t cxxbridge1$cxx_string$length(const std::string &s) noexcept {
 
 void cxxbridge1$cxx_string$clear(std::string &s) noexcept { s.clear(); }
 
+std::size_t cxxbridge1$cxx_string$capacity(const std::string &s) noexcept {
+  return s.capacity();
+}
+
 void cxxbridge1$cxx_string$reserve_total(std::string &s,
                                          size_t new_cap) noexcept {
   s.reserve(new_cap);
 }
 
+void cxxbridge1$cxx_string$shrink_to_fit(std::string &s) noexcept {
+  s.shrink_to_fit();
+}
+
 void cxxbridge1$cxx_string$push(std::string &s, const std::uint8_t *ptr,
                                 std::size_t len) noexcept {
   s.append(reinterpret_cast<const char *>(ptr), len);
@@ -1,11 +1,15 @@
 use crate::actually_private::Private;
 #[cfg(any(feature = "c++17", feature = "c++20"))]
-use crate::CxxStringView;
+use crate::{CxxStringView, CxxStringViewOf};
 use crate::lossy;
+#[cfg(any(feature = "alloc", feature = "c++17", feature = "c++20"))]
+use crate::UniquePtr;
 #[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::fmt::{self, Debug, Display};
 use core::hash::{Hash, Hasher};
@@ -18,6 +22,9 @@ use core::str::{self, Utf8Error};
 extern "C" {
     #[link_name = "cxxbridge1$cxx_string$init"]
     fn string_init(this: &mut MaybeUninit<CxxString>, ptr: *const u8, len: usize);
+    #[cfg(any(feature = "alloc", feature = "c++17", feature = "c++20"))]
+    #[link_name = "cxxbridge1$cxx_string$new"]
+    fn string_new(ptr: *const u8, len: usize) -> *mut CxxString;
     #[link_name = "cxxbridge1$cxx_string$destroy"]
     fn string_destroy(this: &mut MaybeUninit<CxxString>);
     #[link_name = "cxxbridge1$cxx_string$data"]
@@ -26,8 +33,12 @@ extern "C" {
     fn string_length(this: &CxxString) -> usize;
     #[link_name = "cxxbridge1$cxx_string$clear"]
     fn string_clear(this: Pin<&mut CxxString>);
+    #[link_name = "cxxbridge1$cxx_string$capacity"]
+    fn string_capacity(this: &CxxString) -> usize;
     #[link_name = "cxxbridge1$cxx_string$reserve_total"]
     fn string_reserve_total(this: Pin<&mut CxxString>, new_cap: usize);
+    #[link_name = "cxxbridge1$cxx_string$shrink_to_fit"]
+    fn string_shrink_to_fit(this: Pin<&mut CxxString>);
     #[link_name = "cxxbridge1$cxx_string$push"]
     fn string_push(this: Pin<&mut CxxString>, ptr: *const u8, len: usize);
 }
@@ -115,6 +126,15 @@ impl CxxString {
         self.len() == 0
     }
 
+    /// Returns the number of bytes the string can hold without reallocating.
+    ///
+    /// Matches the behavior of C++ [std::string::capacity][capacity].
+    ///
+    /// [capacity]: https://en.cppreference.com/w/cpp/string/basic_string/capacity
+    pub fn capacity(&self) -> usize {
+        unsafe { string_capacity(self) }
+    }
+
     /// Returns a byte slice of this string's contents.
     pub fn as_bytes(&self) -> &[u8] {
         let data = self.as_ptr();
@@ -154,6 +174,18 @@ impl CxxString {
         CxxStringView::new(self.as_bytes())
     }
 
+    /// Constructs a string view containing the entire contents of this string,
+    /// which keeps track of this string as its origin.
+    ///
+    /// This is like [`to_string_view`][to_string_view], but the string can
+    /// later be recovered from the view via [`CxxStringViewOf::origin`].
+    ///
+    /// [to_string_view]: #method.to_string_view
+    #[cfg(any(feature = "c++17", feature = "c++20"))]
+    pub fn to_string_view_of(&self) -> CxxStringViewOf {
+        CxxStringViewOf::new(self)
+    }
+
     /// If the contents of the C++ string are valid UTF-8, this function returns
     /// a view as a Cow::Borrowed &amp;str. Otherwise replaces any invalid UTF-8
     /// sequences with the U+FFFD [replacement character] and returns a
@@ -209,6 +241,18 @@ impl CxxString {
         unsafe { string_reserve_total(self, new_cap) }
     }
 
+    /// Requests that the string's capacity be reduced to fit its length.
+    ///
+    /// Matches the behavior of C++ [std::string::shrink_to_fit][shrink]. The
+    /// request is non-binding; the C++ standard library is free to keep the
+    /// existing capacity. If it does reallocate, all pointers into the string
+    /// contents, including any string views, are invalidated.
+    ///
+    /// [shrink]: https://en.cppreference.com/w/cpp/string/basic_string/shrink_to_fit
+    pub fn shrink_to_fit(self: Pin<&mut Self>) {
+        unsafe { string_shrink_to_fit(self) }
+    }
+
     /// Appends a given string slice onto the end of this C++ string.
     pub fn push_str(self: Pin<&mut Self>, s: &str) {
         self.push_bytes(s.as_bytes());
@@ -220,6 +264,46 @@ impl CxxString {
     }
 }
 
+/// Adapter for formatting directly into a C++ string with `write!`.
+///
+/// `fmt::Write` cannot be implemented on `Pin<&mut CxxString>` itself, so
+/// this wraps the pinned reference and appends each piece of formatted output
+/// with [`CxxString::push_str`].
+///
+/// # Example
+///
+/// ```
+/// use core::fmt::Write;
+/// use cxx::{let_cxx_string, CxxStringWriter};
+///
+/// let_cxx_string!(s = "x = ");
+/// write!(CxxStringWriter::new(s.as_mut()), "{}", 1).unwrap();
+/// assert_eq!(s.to_str().unwrap(), "x = 1");
+/// ```
+pub struct CxxStringWriter<'a> {
+    string: Pin<&'a mut CxxString>,
+}
+
+impl<'a> CxxStringWriter<'a> {
+    /// Wraps a pinned C++ string so that formatted output can be appended
+    /// onto the end of it.
+    pub fn new(string: Pin<&'a mut CxxString>) -> Self {
+        CxxStringWriter { string }
+    }
+
+    /// Returns the wrapped C++ string.
+    pub fn into_inner(self) -> Pin<&'a mut CxxString> {
+        self.string
+    }
+}
+
+impl<'a> fmt::Write for CxxStringWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.string.as_mut().push_str(s);
+        Ok(())
+    }
+}
+
 impl Display for CxxString {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         lossy::display(self.as_bytes(), f)
@@ -270,6 +354,24 @@ impl Hash for CxxString {
     }
 }
 
+// Allocates a new std::string on the C++ heap holding a copy of `bytes`.
+#[cfg(any(feature = "alloc", feature = "c++17", feature = "c++20"))]
+pub(crate) fn new_unique(bytes: &[u8]) -> UniquePtr<CxxString> {
+    unsafe { UniquePtr::from_raw(string_new(bytes.as_ptr(), bytes.len())) }
+}
+
+/// Hands bytes built up in Rust over to a new C++ `std::string` owned by a
+/// `UniquePtr`.
+///
+/// A `std::string` cannot adopt a buffer allocated by Rust, so the bytes are
+/// copied exactly once, straight into a string constructed at its final size
+/// rather than grown incrementally, and the `Vec` is then freed.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn into_cxx_string(bytes: Vec<u8>) -> UniquePtr<CxxString> {
+    new_unique(&bytes)
+}
+
 #[doc(hidden)]
 #[repr(C)]
 pub struct StackString {
@@ -1,16 +1,32 @@
 use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::borrow::Borrow;
+use core::char;
 use core::cmp::Ordering;
-use core::convert::AsRef;
-use core::fmt::{self, Debug, Display};
+use core::convert::{AsRef, TryFrom};
+use core::fmt::{self, Debug, Display, Write as _};
 use core::hash::{Hash, Hasher};
+use core::iter;
 use core::marker::PhantomData;
-use core::mem::MaybeUninit;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, Range};
+use core::pin::Pin;
 use core::slice;
-use core::str::{self, Utf8Error};
+use core::str::{self, FromStr, Utf8Error};
+#[cfg(feature = "std")]
+use std::ffi::{CStr, OsStr};
+#[cfg(feature = "std")]
+use std::io::{Cursor, Read};
+#[cfg(all(unix, feature = "std"))]
+use std::{ffi::OsString, path::PathBuf};
 
-use crate::CxxString;
+use crate::lossy;
+use crate::string;
+use crate::{CxxString, UniquePtr};
 
 extern "C" {
     #[link_name = "cxxbridge1$cxx_string_view$init"]
@@ -29,6 +45,128 @@ pub struct CxxStringView<'a> {
     _phantom: PhantomData<&'a [u8]>,
 }
 
+/// A pattern to search for in a [`CxxStringView`], accepted by methods such as
+/// [`find`][CxxStringView::find], [`split`][CxxStringView::split], and
+/// [`trim_matches`][CxxStringView::trim_matches].
+///
+/// These methods take any `impl Into<ViewPattern>`, so a `u8`, a `&[u8]`, or a
+/// closure `FnMut(u8) -> bool` can be passed directly in place of the enum.
+pub enum ViewPattern<'p, F = fn(u8) -> bool> {
+    /// Matches exactly this byte.
+    Byte(u8),
+    /// Matches any one of the bytes in the set.
+    Set(&'p [u8]),
+    /// Matches any byte for which the predicate returns true.
+    Func(F),
+}
+
+impl<'p, F> ViewPattern<'p, F>
+where
+    F: FnMut(u8) -> bool,
+{
+    fn matches(&mut self, b: u8) -> bool {
+        match self {
+            ViewPattern::Byte(byte) => *byte == b,
+            ViewPattern::Set(set) => set.contains(&b),
+            ViewPattern::Func(f) => f(b),
+        }
+    }
+
+    fn find_in(&mut self, haystack: &[u8]) -> Option<usize> {
+        match self {
+            ViewPattern::Byte(byte) => find_byte(*byte, haystack),
+            #[cfg(feature = "memchr")]
+            ViewPattern::Set(&[b1, b2]) => memchr::memchr2(b1, b2, haystack),
+            #[cfg(feature = "memchr")]
+            ViewPattern::Set(&[b1, b2, b3]) => memchr::memchr3(b1, b2, b3, haystack),
+            _ => haystack.iter().position(|&b| self.matches(b)),
+        }
+    }
+}
+
+impl<'p> From<u8> for ViewPattern<'p> {
+    fn from(byte: u8) -> Self {
+        ViewPattern::Byte(byte)
+    }
+}
+
+impl<'p> From<&'p [u8]> for ViewPattern<'p> {
+    fn from(set: &'p [u8]) -> Self {
+        ViewPattern::Set(set)
+    }
+}
+
+impl<'p, F> From<F> for ViewPattern<'p, F>
+where
+    F: FnMut(u8) -> bool,
+{
+    fn from(f: F) -> Self {
+        ViewPattern::Func(f)
+    }
+}
+
+/// Error returned by [`CxxStringView::split_at_checked`] when the split index
+/// is past the end of the view.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SplitError {
+    mid: usize,
+    len: usize,
+}
+
+impl SplitError {
+    /// The index at which the split was requested.
+    pub fn mid(&self) -> usize {
+        self.mid
+    }
+
+    /// The length of the view that was being split.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "split index {} is out of range for string view of length {}",
+            self.mid, self.len,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for SplitError {}
+
+/// Error returned by [`CxxStringView::try_from_raw_parts`] when the requested
+/// length is too large for any valid slice.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ViewError {
+    len: usize,
+}
+
+impl ViewError {
+    /// The length that was rejected.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Display for ViewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "string view length {} exceeds the maximum of isize::MAX bytes",
+            self.len,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for ViewError {}
+
 impl CxxStringView<'static> {
     /// Constructs an empty string view.
     ///
@@ -64,6 +202,47 @@ impl<'a> CxxStringView<'a> {
         result
     }
 
+    /// Constructs a string view containing the first `len` bytes of the array
+    /// starting at `data`, returning an error instead of producing an invalid
+    /// view if `len` exceeds `isize::MAX`.
+    ///
+    /// No slice may be longer than `isize::MAX` bytes, so a larger `len` can
+    /// only come from a corrupted or untrusted source.
+    ///
+    /// SAFETY:
+    ///   Either `len` must be 0, or `data` and `len` must satisfy the safety invariants of [`core::slice::from_raw_parts<'a, u8>`][slice].
+    ///   The length check is performed first, so an over-large `len` is
+    ///   rejected regardless of `data`.
+    ///
+    /// [slice]: core::slice::from_raw_parts
+    pub unsafe fn try_from_raw_parts(data: *const u8, len: usize) -> Result<Self, ViewError> {
+        if len > isize::MAX as usize {
+            return Err(ViewError { len });
+        }
+        Ok(unsafe { Self::from_raw_parts(data, len) })
+    }
+
+    /// Constructs a string view containing the first `len` bytes of the array
+    /// starting at `data`, or returns `None` if `len` is greater than `max`.
+    ///
+    /// This is for sanitizing a `(data, len)` pair from an untrusted source
+    /// such as a deserialized structure, where `max` is the known size of the
+    /// buffer that `data` points into. A `len` exceeding `isize::MAX` is
+    /// rejected as in [`try_from_raw_parts`][try_from_raw_parts], whatever
+    /// `max` is.
+    ///
+    /// SAFETY:
+    ///   Either `max` must be 0, or `data` and `max` must satisfy the safety invariants of [`core::slice::from_raw_parts<'a, u8>`][slice].
+    ///
+    /// [try_from_raw_parts]: #method.try_from_raw_parts
+    /// [slice]: core::slice::from_raw_parts
+    pub unsafe fn from_raw_parts_bounded(data: *const u8, len: usize, max: usize) -> Option<Self> {
+        if len > max {
+            return None;
+        }
+        unsafe { Self::try_from_raw_parts(data, len) }.ok()
+    }
+
     /// Constructs a string view from a reference to a `[u8]`. The string view is
     /// live as long as the backing slice.
     ///
@@ -77,6 +256,18 @@ impl<'a> CxxStringView<'a> {
         unsafe { Self::from_raw_parts(data, len) }
     }
 
+    /// Constructs a string view of the contents of a C string, not including
+    /// the nul terminator.
+    ///
+    /// Loosely matches the behavior of C++ [std::string_view::string_view][ctors] #4.
+    ///
+    /// [ctors]: https://en.cppreference.com/w/cpp/string/basic_string_view/basic_string_view
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn from_cstr(cstr: &'a CStr) -> Self {
+        CxxStringView::new(cstr.to_bytes())
+    }
+
     /// Returns the length of the string view in bytes.
     ///
     /// Matches the behavior of C++ [std::string_view::size][size].
@@ -111,16 +302,78 @@ impl<'a> CxxStringView<'a> {
     }
 
     /// Returns a byte slice of this string view's contents.
+    ///
+    /// The returned slice is always valid for reads, including when the view
+    /// is empty. A C++ `std::string_view` may have a null data pointer if its
+    /// size is zero, but the slice's pointer is never null; it is then a
+    /// well-aligned dangling pointer, as for any empty Rust slice. An empty
+    /// view whose data pointer is non-null has that pointer preserved.
+    ///
+    /// # Panics
+    ///
+    /// With debug assertions enabled, panics if the view is evidently
+    /// invalid: a null data pointer with a non-zero length, a length greater
+    /// than `isize::MAX`, or a range that wraps around the address space.
+    /// These checks cannot detect a view whose backing data has been freed in
+    /// general, but do catch some of the symptoms.
     pub fn as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    /// Returns a byte slice of this string view's contents, without the check
+    /// for a null data pointer that [`as_bytes`][as_bytes] performs.
+    ///
+    /// This saves a branch in hot loops where the origin of the view is known.
+    ///
+    /// # Safety
+    ///
+    /// The view's data pointer must not be null. That is always the case for
+    /// views constructed in Rust, such as by [`new`][new] or
+    /// [`CxxString::to_string_view`], but a view received from C++ may be
+    /// empty with a null data pointer, for example a default-constructed
+    /// `std::string_view`.
+    ///
+    /// [as_bytes]: #method.as_bytes
+    /// [new]: #method.new
+    pub unsafe fn as_bytes_unchecked(&self) -> &'a [u8] {
+        let data = self.as_ptr();
+        debug_assert!(!data.is_null(), "string view has a null data pointer");
+        unsafe { slice::from_raw_parts(data, self.len()) }
+    }
+
+    // Same as `as_bytes`, but the slice borrows the backing data for the
+    // lifetime of the view rather than for the lifetime of `&self`. Used to
+    // produce sub-views.
+    fn as_slice(&self) -> &'a [u8] {
         let data = self.as_ptr();
         let len = self.len();
 
+        // A view that outlived its backing data, or that was built from bogus
+        // raw parts on the C++ side, often shows up as one of these. None of
+        // them can be caught in general, but the cheap ones are worth
+        // catching in debug builds before handing out an invalid slice.
+        debug_assert!(
+            !data.is_null() || len == 0,
+            "string view has a null data pointer but a length of {}",
+            len,
+        );
+        debug_assert!(
+            len <= isize::MAX as usize,
+            "string view length {} exceeds isize::MAX",
+            len,
+        );
+        debug_assert!(
+            (data as usize).checked_add(len).is_some(),
+            "string view of length {} at {:p} wraps around the address space",
+            len,
+            data,
+        );
+
         // string_view's data can be nullptr if its size is zero, but
         // a slice's data isn't allowed to be null.
         let data = if !data.is_null() {
             data
         } else {
-            debug_assert_eq!(len, 0);
             core::ptr::NonNull::dangling().as_ptr()
         };
 
@@ -130,12 +383,431 @@ impl<'a> CxxStringView<'a> {
         unsafe { slice::from_raw_parts(data, len) }
     }
 
+    /// Returns whether the data of this string view is suitably aligned to be
+    /// reinterpreted as a slice of `T`.
+    ///
+    /// Only the alignment of the start of the view is checked, not whether its
+    /// length is a multiple of `size_of::<T>()`. An empty view is always
+    /// considered aligned, since an empty `[T]` needs no data.
+    pub fn is_aligned_for<T>(&self) -> bool {
+        self.is_empty() || check_alignment::<T>(self.as_ptr())
+    }
+
+    /// Reinterprets the data of this string view as UTF-16 code units in
+    /// native byte order, without copying.
+    ///
+    /// Returns `None` if the length is odd or if the data is not aligned for
+    /// `u16` (see [`is_aligned_for`][is_aligned_for]). The code units are not
+    /// validated; use [`char::decode_utf16`] to decode them.
+    ///
+    /// [is_aligned_for]: #method.is_aligned_for
+    pub fn reinterpret_utf16(&self) -> Option<&'a [u16]> {
+        let bytes = self.as_slice();
+        if bytes.is_empty() {
+            return Some(&[]);
+        }
+        if bytes.len() % 2 != 0 || !check_alignment::<u16>(bytes.as_ptr()) {
+            return None;
+        }
+        // Safety: the pointer is non-null and aligned for u16, the length in
+        // bytes is even, and any bit pattern is a valid u16.
+        Some(unsafe { slice::from_raw_parts(bytes.as_ptr().cast::<u16>(), bytes.len() / 2) })
+    }
+
+    /// Reinterprets the bytes of this string view as `i8`, for passing to C
+    /// APIs that take `signed char *`.
+    ///
+    /// This is a pure reinterpretation of the same memory with no copying or
+    /// conversion; each byte above 0x7F reads as the corresponding negative
+    /// value in two's complement.
+    pub fn as_i8_slice(&self) -> &'a [i8] {
+        let bytes = self.as_slice();
+        // Safety: i8 has the same size, alignment, and validity as u8.
+        unsafe { slice::from_raw_parts(bytes.as_ptr().cast::<i8>(), bytes.len()) }
+    }
+
+    /// Returns the byte at `index`, or `default` if `index` is out of range.
+    pub fn get_or(&self, index: usize, default: u8) -> u8 {
+        self.as_bytes().get(index).copied().unwrap_or(default)
+    }
+
+    /// Returns an iterator over the bytes of this string view in reverse
+    /// order, from the last byte to the first.
+    pub fn bytes_rev(&self) -> impl Iterator<Item = u8> + 'a {
+        self.as_slice().iter().rev().copied()
+    }
+
+    /// Returns which byte values occur in this string view.
+    ///
+    /// Element `b` of the returned table is true if and only if the byte `b`
+    /// appears at least once.
+    pub fn distinct_bytes(&self) -> [bool; 256] {
+        let mut present = [false; 256];
+        for &b in self.as_bytes() {
+            present[b as usize] = true;
+        }
+        present
+    }
+
+    /// Counts the occurrences of each byte value in this string view.
+    ///
+    /// Element `b` of the returned table is the number of times the byte `b`
+    /// appears.
+    pub fn byte_histogram(&self) -> [usize; 256] {
+        let mut counts = [0; 256];
+        for &b in self.as_bytes() {
+            counts[b as usize] += 1;
+        }
+        counts
+    }
+
+    /// Returns true if the bytes of this string view are in non-decreasing
+    /// order, as by `[u8]::is_sorted`.
+    ///
+    /// Empty and single-byte views are always sorted.
+    pub fn is_sorted_bytes(&self) -> bool {
+        self.as_bytes().windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    /// Estimates the Shannon entropy of this string view's bytes, in bits per
+    /// byte.
+    ///
+    /// The result ranges from 0.0, for a view consisting of a single repeated
+    /// byte value, up to 8.0, for a view in which all 256 byte values are
+    /// equally frequent. Text typically falls well below compressed or
+    /// encrypted data. An empty view has an entropy of 0.0.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn shannon_entropy(&self) -> f64 {
+        let len = self.len() as f64;
+        let mut entropy = 0.0;
+        for &count in self.byte_histogram().iter() {
+            if count != 0 {
+                let p = count as f64 / len;
+                entropy -= p * p.log2();
+            }
+        }
+        entropy
+    }
+
+    /// Returns the byte offset of the first position at which this string view
+    /// and `other` differ, or `None` if they are equal.
+    ///
+    /// If one view is a proper prefix of the other, the result is the length
+    /// of the shorter one, i.e. the offset where one ends and the other does
+    /// not.
+    pub fn first_difference(&self, other: &CxxStringView) -> Option<usize> {
+        let this = self.as_bytes();
+        let other = other.as_bytes();
+        match this.iter().zip(other).position(|(a, b)| a != b) {
+            Some(i) => Some(i),
+            None if this.len() != other.len() => Some(this.len().min(other.len())),
+            None => None,
+        }
+    }
+
+    /// Binary searches this string view, whose bytes are assumed to be sorted,
+    /// for the byte `needle`.
+    ///
+    /// Behaves the same as [`slice::binary_search`][binary_search] on the
+    /// view's bytes: returns `Ok` with the index of a matching byte, or `Err`
+    /// with the index where `needle` could be inserted to keep the bytes
+    /// sorted. If the bytes are not sorted the result is unspecified.
+    ///
+    /// [binary_search]: https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search
+    pub fn binary_search_byte(&self, needle: u8) -> Result<usize, usize> {
+        self.as_bytes().binary_search(&needle)
+    }
+
+    /// Returns true if every byte of this string view is ASCII whitespace, as
+    /// defined by [`u8::is_ascii_whitespace`], including when the view is
+    /// empty.
+    ///
+    /// This is the same as checking that [`trim_ascii`][trim_ascii] leaves an
+    /// empty view, and is handy for skipping blank lines.
+    ///
+    /// [trim_ascii]: #method.trim_ascii
+    pub fn is_ascii_whitespace_only(&self) -> bool {
+        self.as_bytes().iter().all(u8::is_ascii_whitespace)
+    }
+
+    /// Returns the sub-view with leading and trailing ASCII whitespace
+    /// removed.
+    ///
+    /// Matches the behavior of [`[u8]::trim_ascii`][trim_ascii]: whitespace
+    /// is as defined by [`u8::is_ascii_whitespace`].
+    ///
+    /// [trim_ascii]: https://doc.rust-lang.org/std/primitive.slice.html#method.trim_ascii
+    pub fn trim_ascii(&self) -> CxxStringView<'a> {
+        CxxStringView::new(trim_ascii_whitespace(self.as_slice()))
+    }
+
+    /// Returns the sub-view with leading ASCII whitespace removed.
+    ///
+    /// Matches the behavior of [`[u8]::trim_ascii_start`][trim_ascii_start].
+    ///
+    /// [trim_ascii_start]: https://doc.rust-lang.org/std/primitive.slice.html#method.trim_ascii_start
+    pub fn trim_ascii_start(&self) -> CxxStringView<'a> {
+        CxxStringView::new(trim_ascii_whitespace_start(self.as_slice()))
+    }
+
+    /// Returns the sub-view with trailing ASCII whitespace removed.
+    ///
+    /// Matches the behavior of [`[u8]::trim_ascii_end`][trim_ascii_end].
+    ///
+    /// [trim_ascii_end]: https://doc.rust-lang.org/std/primitive.slice.html#method.trim_ascii_end
+    pub fn trim_ascii_end(&self) -> CxxStringView<'a> {
+        CxxStringView::new(trim_ascii_whitespace_end(self.as_slice()))
+    }
+
+    /// Returns the sub-view with trailing nul bytes removed.
+    ///
+    /// This is useful for viewing a fixed-size C `char` buffer which is
+    /// padded with `\0` after its contents. Nul bytes elsewhere in the view
+    /// are kept.
+    pub fn trim_end_nul(&self) -> CxxStringView<'a> {
+        let bytes = self.as_slice();
+        let end = bytes.iter().rposition(|&b| b != b'\0').map_or(0, |i| i + 1);
+        CxxStringView::new(&bytes[..end])
+    }
+
+    /// Returns the sub-view up to but not including the first nul byte, or
+    /// the whole view if it contains no nul.
+    ///
+    /// This is the string a C function would see if handed this buffer,
+    /// as measured by `strlen`, except that it never reads past the end of
+    /// the view. Compare [`trim_end_nul`][trim_end_nul], which only removes
+    /// trailing padding.
+    ///
+    /// [trim_end_nul]: #method.trim_end_nul
+    pub fn c_str_view(&self) -> CxxStringView<'a> {
+        let bytes = self.as_slice();
+        let end = find_byte(b'\0', bytes).unwrap_or(bytes.len());
+        CxxStringView::new(&bytes[..end])
+    }
+
+    /// Returns true if the two string views are equal after ignoring any
+    /// leading and trailing ASCII whitespace on both.
+    pub fn eq_trimmed(&self, other: &CxxStringView) -> bool {
+        trim_ascii_whitespace(self.as_bytes()) == trim_ascii_whitespace(other.as_bytes())
+    }
+
+    /// Compares the string view against `other`, returning the length of
+    /// their common prefix along with what remains of each side after it.
+    ///
+    /// The remainders start at the first point where the two sides differ,
+    /// so both are empty exactly when the contents are equal. The prefix
+    /// length is in bytes and is rounded down to a char boundary of `other`
+    /// if needed, so that the `&str` remainder can be sliced; in that case
+    /// both remainders begin with the whole of the partially shared char.
+    ///
+    /// This is mainly intended for producing readable assertion failures.
+    pub fn diff_str<'b>(&self, other: &'b str) -> (usize, CxxStringView<'a>, &'b str) {
+        let bytes = self.as_slice();
+        let mut common = bytes
+            .iter()
+            .zip(other.as_bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !other.is_char_boundary(common) {
+            common -= 1;
+        }
+        (
+            common,
+            CxxStringView::new(&bytes[common..]),
+            &other[common..],
+        )
+    }
+
+    /// Returns the number of byte positions at which the two string views
+    /// differ, or `None` if their lengths differ.
+    pub fn hamming_distance(&self, other: &CxxStringView) -> Option<usize> {
+        let (a, b) = (self.as_bytes(), other.as_bytes());
+        if a.len() != b.len() {
+            return None;
+        }
+        Some(a.iter().zip(b).filter(|(x, y)| x != y).count())
+    }
+
+    /// Interprets the string view as a boolean config value.
+    ///
+    /// Returns `Some(true)` for `1` or `true`, and `Some(false)` for `0` or
+    /// `false`, with `true` and `false` matched ignoring ASCII case. Anything
+    /// else returns `None`, including surrounding whitespace, so combine with
+    /// [`trim_ascii`][trim] if that should be allowed.
+    ///
+    /// [trim]: #method.trim_ascii
+    pub fn eq_bool(&self) -> Option<bool> {
+        let bytes = self.as_bytes();
+        if bytes == b"1" || bytes.eq_ignore_ascii_case(b"true") {
+            Some(true)
+        } else if bytes == b"0" || bytes.eq_ignore_ascii_case(b"false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Parses the string view as a number, or any other type implementing
+    /// `FromStr`.
+    ///
+    /// The view must be valid UTF-8 and the whole of it is passed to
+    /// `T::from_str`, so the accepted forms are exactly those of that impl;
+    /// for the primitive integer and float types this means no surrounding
+    /// whitespace is allowed. Returns `None` if the view is not UTF-8 or
+    /// fails to parse.
+    pub fn eq_number<T: FromStr>(&self) -> Option<T> {
+        self.to_str().ok()?.parse().ok()
+    }
+
+    /// Returns the bytes of this string view if every one of them is ASCII,
+    /// otherwise `None`.
+    ///
+    /// A successful result is also guaranteed to be valid UTF-8, so callers
+    /// can skip any further encoding checks.
+    pub fn as_ascii(&self) -> Option<&'a [u8]> {
+        let bytes = self.as_slice();
+        if bytes.is_ascii() {
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the contents of this string view as a `&str` if every byte is
+    /// ASCII, otherwise `None`.
+    ///
+    /// ASCII is always valid UTF-8, so this only needs the cheap ASCII check
+    /// rather than full UTF-8 validation as in [`to_str`][to_str]. Views
+    /// containing any non-ASCII byte return `None` even if they are valid
+    /// UTF-8.
+    ///
+    /// [to_str]: #method.to_str
+    pub fn to_str_if_ascii(&self) -> Option<&'a str> {
+        let bytes = self.as_ascii()?;
+        // Safety: ASCII is valid UTF-8.
+        Some(unsafe { str::from_utf8_unchecked(bytes) })
+    }
+
+    /// Splits the string view at its first invalid UTF-8 byte, returning the
+    /// longest valid UTF-8 prefix as a `&str` along with the remainder.
+    ///
+    /// The remaining view starts at the first byte that is not part of a
+    /// valid sequence, or is empty if the whole view is valid UTF-8. An
+    /// incomplete sequence at the very end is also left in the remainder,
+    /// so incremental decoders can retry it once more bytes arrive.
+    pub fn split_valid_utf8(&self) -> (&'a str, CxxStringView<'a>) {
+        let bytes = self.as_slice();
+        let valid_up_to = match str::from_utf8(bytes) {
+            Ok(_) => bytes.len(),
+            Err(error) => error.valid_up_to(),
+        };
+        let (valid, rest) = bytes.split_at(valid_up_to);
+        // Safety: from_utf8 validated the bytes up to valid_up_to.
+        let valid = unsafe { str::from_utf8_unchecked(valid) };
+        (valid, CxxStringView::new(rest))
+    }
+
+    /// Returns a new string view of the same bytes, borrowed for no longer than
+    /// `self`.
+    ///
+    /// `CxxStringView` is not `Copy`, so this is the way to hand a view to a
+    /// function that takes `CxxStringView<'_>` by value while keeping the
+    /// original for later use.
+    pub fn reborrow<'b>(&'b self) -> CxxStringView<'b>
+    where
+        'a: 'b,
+    {
+        CxxStringView::new(self.as_slice())
+    }
+
+    /// Captures the pointer and length of this string view into a
+    /// [`ViewSnapshot`], whose accessors then run entirely in Rust.
+    ///
+    /// Prefer this over calling [`len`][len] and [`as_bytes`][as_bytes]
+    /// repeatedly on the same view in a tight loop, since each of those calls
+    /// into C++.
+    ///
+    /// [len]: #method.len
+    /// [as_bytes]: #method.as_bytes
+    pub fn snapshot(&self) -> ViewSnapshot<'a> {
+        ViewSnapshot {
+            bytes: self.as_slice(),
+        }
+    }
+
+    /// Validates the string view as UTF-8 and splits it into two `&str`
+    /// halves at byte index `mid`.
+    ///
+    /// Returns `None` if the view is not valid UTF-8, or if `mid` is past the
+    /// end of the view or not on a char boundary.
+    pub fn split_at_char_boundary(&self, mid: usize) -> Option<(&'a str, &'a str)> {
+        let s = str::from_utf8(self.as_slice()).ok()?;
+        if s.is_char_boundary(mid) {
+            Some(s.split_at(mid))
+        } else {
+            None
+        }
+    }
+
     /// Validates that the C++ string view contains UTF-8 data and produces a view of
     /// it as a Rust &amp;str, otherwise an error.
     pub fn to_str(&self) -> Result<&str, Utf8Error> {
         str::from_utf8(self.as_bytes())
     }
 
+    /// Returns an object that displays this string view lossily, without first
+    /// building a `String`.
+    ///
+    /// The output is the same as that of the `Display` impl of `CxxStringView`
+    /// itself, with invalid UTF-8 sequences replaced by U+FFFD. Unless a width
+    /// or precision is given, the valid runs and replacement characters are
+    /// written to the formatter one at a time rather than collected into a
+    /// temporary allocation; padding and truncation need the whole string, so
+    /// flags such as `{:>10}` or `{:.3}` fall back to formatting a lossily
+    /// converted copy.
+    pub fn as_display(&self) -> impl Display + 'a {
+        let bytes = self.as_slice();
+        crate::fmt::display(move |f| {
+            if f.width().is_none() && f.precision().is_none() {
+                lossy::display(bytes, f)
+            } else {
+                Display::fmt(&*String::from_utf8_lossy(bytes), f)
+            }
+        })
+    }
+
+    /// Formats the string view like its `Display` impl, but fails instead of
+    /// substituting replacement characters if it is not valid UTF-8.
+    ///
+    /// This is meant to be called from a `Display` impl of a wrapper type;
+    /// [`strict`][strict] returns a ready-made one. See [`Strict`] for what
+    /// is written on invalid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// On invalid UTF-8 this returns an error even though the formatter did
+    /// not fail, which makes `ToString::to_string` and `format!` panic when
+    /// they format a wrapper that calls it. Use `write!` into a writer that
+    /// reports errors instead.
+    ///
+    /// [strict]: #method.strict
+    pub fn fmt_strict(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.strict(), f)
+    }
+
+    /// Returns an object that implements `Display` by formatting this view as
+    /// UTF-8, failing instead of substituting replacement characters if it is
+    /// not valid UTF-8.
+    ///
+    /// See [`Strict`] for the behavior on invalid UTF-8, including why it must
+    /// not be passed to `to_string` or `format!`.
+    pub fn strict(&self) -> Strict<'a> {
+        Strict {
+            bytes: self.as_slice(),
+        }
+    }
+
     /// If the contents of the C++ string view are valid UTF-8, this function returns
     /// a view as a Cow::Borrowed &amp;str. Otherwise replaces any invalid UTF-8
     /// sequences with the U+FFFD [replacement character] and returns a
@@ -145,78 +817,1978 @@ impl<'a> CxxStringView<'a> {
     pub fn to_string_lossy(&self) -> Cow<str> {
         String::from_utf8_lossy(self.as_bytes())
     }
-}
 
-impl<'a> Display for CxxStringView<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(self.to_string_lossy().as_ref(), f)
+    /// Validates that the C++ string view contains UTF-8 data and returns it
+    /// as a `Cow::Borrowed` &amp;str, otherwise an error.
+    ///
+    /// This is the strict counterpart to [`to_string_lossy`][lossy], for
+    /// code that wants to handle both the same way. It never allocates, and
+    /// unlike [`to_str`][to_str] the result borrows for the lifetime of the
+    /// backing data rather than of the view.
+    ///
+    /// [lossy]: #method.to_string_lossy
+    /// [to_str]: #method.to_str
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_cow_str(&self) -> Result<Cow<'a, str>, Utf8Error> {
+        str::from_utf8(self.as_slice()).map(Cow::Borrowed)
     }
-}
 
-impl<'a> Debug for CxxStringView<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Debug::fmt(self.to_string_lossy().as_ref(), f)
+    /// Returns the sub-view starting `n` bytes into this string view, or
+    /// `None` if `n` is greater than `len()`.
+    ///
+    /// This is a checked, non-mutating counterpart to C++
+    /// [std::string_view::remove_prefix][remove_prefix]. Advancing by exactly
+    /// `len()` bytes produces an empty view.
+    ///
+    /// [remove_prefix]: https://en.cppreference.com/w/cpp/string/basic_string_view/remove_prefix
+    pub fn advance(&self, n: usize) -> Option<CxxStringView<'a>> {
+        self.as_slice().get(n..).map(CxxStringView::new)
     }
-}
 
-impl<'a> PartialEq for CxxStringView<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.as_bytes() == other.as_bytes()
+    /// Returns the sub-view from byte index `from` to the end of this string
+    /// view.
+    ///
+    /// Equivalent to C++ `substr(from)`. A `from` of exactly `len()` produces
+    /// an empty view; use [`advance`][advance] for a non-panicking version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is greater than `len()`.
+    ///
+    /// [advance]: #method.advance
+    pub fn suffix(&self, from: usize) -> CxxStringView<'a> {
+        let bytes = self.as_slice();
+        match bytes.get(from..) {
+            Some(suffix) => CxxStringView::new(suffix),
+            None => panic!(
+                "suffix index {} is out of range for string view of length {}",
+                from,
+                bytes.len(),
+            ),
+        }
     }
-}
 
-impl<'a> PartialEq<CxxStringView<'a>> for str {
-    fn eq(&self, other: &CxxStringView<'a>) -> bool {
-        self.as_bytes() == other.as_bytes()
+    /// Returns the closest byte index at or before `index` that lies on a
+    /// UTF-8 char boundary.
+    ///
+    /// Mirrors `str::floor_char_boundary`. An index is considered a boundary
+    /// if it is 0, `len()`, or the byte at that index is not a UTF-8
+    /// continuation byte; the view's contents need not be valid UTF-8 overall.
+    /// An `index` greater than `len()` returns `len()`.
+    pub fn floor_char_boundary(&self, index: usize) -> usize {
+        let bytes = self.as_bytes();
+        if index >= bytes.len() {
+            return bytes.len();
+        }
+        let mut i = index;
+        while i > 0 && is_utf8_continuation(bytes[i]) {
+            i -= 1;
+        }
+        i
     }
-}
 
-impl<'a> PartialEq<str> for CxxStringView<'a> {
-    fn eq(&self, other: &str) -> bool {
-        self.as_bytes() == other.as_bytes()
+    /// Returns the closest byte index at or after `index` that lies on a
+    /// UTF-8 char boundary.
+    ///
+    /// Mirrors `str::ceil_char_boundary`, using the same notion of boundary as
+    /// [`floor_char_boundary`][floor]. An `index` greater than `len()` returns
+    /// `len()`.
+    ///
+    /// [floor]: #method.floor_char_boundary
+    pub fn ceil_char_boundary(&self, index: usize) -> usize {
+        let bytes = self.as_bytes();
+        let mut i = index;
+        while i < bytes.len() && is_utf8_continuation(bytes[i]) {
+            i += 1;
+        }
+        i.min(bytes.len())
     }
-}
 
-impl<'a> PartialEq<CxxStringView<'a>> for CxxString {
-    fn eq(&self, other: &CxxStringView<'a>) -> bool {
-        self.as_bytes() == other.as_bytes()
+    /// Divides the string view into two at byte index `mid`, or returns an
+    /// error recording `mid` and the view's length if `mid > len()`.
+    ///
+    /// The first view contains bytes `[0, mid)` and the second contains bytes
+    /// `[mid, len)`.
+    pub fn split_at_checked(
+        &self,
+        mid: usize,
+    ) -> Result<(CxxStringView<'a>, CxxStringView<'a>), SplitError> {
+        let bytes = self.as_slice();
+        if mid > bytes.len() {
+            return Err(SplitError {
+                mid,
+                len: bytes.len(),
+            });
+        }
+        let (left, right) = bytes.split_at(mid);
+        Ok((CxxStringView::new(left), CxxStringView::new(right)))
     }
-}
 
-impl<'a> PartialEq<CxxString> for CxxStringView<'a> {
-    fn eq(&self, other: &CxxString) -> bool {
-        self.as_bytes() == other.as_bytes()
+    /// Returns the index of the first of `prefixes` that this string view
+    /// starts with, or `None` if it starts with none of them.
+    ///
+    /// Prefixes are tried in order, so when several match, such as `"GET"`
+    /// and `"G"`, the one listed first wins. Put longer prefixes first to
+    /// prefer the most specific match. An empty prefix always matches.
+    pub fn matches_any_prefix<T>(&self, prefixes: &[T]) -> Option<usize>
+    where
+        T: AsRef<[u8]>,
+    {
+        let bytes = self.as_bytes();
+        prefixes
+            .iter()
+            .position(|prefix| bytes.starts_with(prefix.as_ref()))
     }
-}
 
-impl<'a> Eq for CxxStringView<'a> {}
+    /// Returns the index of the first of `prefixes` that this string view
+    /// starts with, or `None` if it starts with none of them.
+    ///
+    /// This is the same as [`matches_any_prefix`][matches_any_prefix], named
+    /// to pair with [`ends_with_any`][ends_with_any].
+    ///
+    /// [matches_any_prefix]: #method.matches_any_prefix
+    /// [ends_with_any]: #method.ends_with_any
+    pub fn starts_with_any<T>(&self, prefixes: &[T]) -> Option<usize>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.matches_any_prefix(prefixes)
+    }
 
-impl<'a> PartialOrd for CxxStringView<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.as_bytes().partial_cmp(other.as_bytes())
+    /// Returns the index of the first of `suffixes` that this string view
+    /// ends with, or `None` if it ends with none of them.
+    ///
+    /// Suffixes are tried in order, so when several match, such as
+    /// `".tar.gz"` and `".gz"`, the one listed first wins. Put longer
+    /// suffixes first to prefer the most specific match. An empty suffix
+    /// always matches.
+    pub fn ends_with_any<T>(&self, suffixes: &[T]) -> Option<usize>
+    where
+        T: AsRef<[u8]>,
+    {
+        let bytes = self.as_bytes();
+        suffixes
+            .iter()
+            .position(|suffix| bytes.ends_with(suffix.as_ref()))
     }
-}
 
-impl<'a> Ord for CxxStringView<'a> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.as_bytes().cmp(other.as_bytes())
+    /// Returns a reference to the first `N` bytes of this string view as an
+    /// array, or `None` if the view is shorter than `N` bytes.
+    ///
+    /// Any bytes after the first `N` are ignored. This is convenient for
+    /// reading fixed-size headers:
+    ///
+    /// ```
+    /// # use cxx::CxxStringView;
+    /// # fn parse(sv: CxxStringView) -> Option<()> {
+    /// let [major, minor] = *sv.as_array()?;
+    /// # assert_eq!((major, minor), (1, 2));
+    /// # Some(())
+    /// # }
+    /// # parse(CxxStringView::new(b"\x01\x02payload")).unwrap();
+    /// ```
+    pub fn as_array<const N: usize>(&self) -> Option<&'a [u8; N]> {
+        let bytes = self.as_slice().get(..N)?;
+        <&[u8; N]>::try_from(bytes).ok()
     }
-}
 
-impl<'a> Hash for CxxStringView<'a> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.as_bytes().hash(state);
+    /// Returns the sub-view of up to `radius` bytes on either side of byte
+    /// position `offset`, clamped to the bounds of this view.
+    ///
+    /// The result covers bytes `[offset - radius, offset + radius)`, so it is
+    /// at most `2 * radius` bytes long. This is meant for showing the text
+    /// near the location of an error. An `offset` past the end is treated as
+    /// the end.
+    pub fn context(&self, offset: usize, radius: usize) -> CxxStringView<'a> {
+        let bytes = self.as_slice();
+        let offset = offset.min(bytes.len());
+        let start = offset.saturating_sub(radius);
+        let end = offset.saturating_add(radius).min(bytes.len());
+        CxxStringView::new(&bytes[start..end])
     }
-}
 
-impl<'a> AsRef<[u8]> for CxxStringView<'a> {
-    fn as_ref(&self) -> &[u8] {
-        self.as_bytes()
+    /// Returns the length of the longest prefix of this string view consisting
+    /// only of bytes contained in `set`.
+    ///
+    /// This is the same as the position of the first byte not in `set`, or
+    /// `len()` if there is none. An empty `set` always gives 0.
+    pub fn prefix_len_in_set(&self, set: &[u8]) -> usize {
+        let bytes = self.as_bytes();
+        bytes
+            .iter()
+            .position(|b| !set.contains(b))
+            .unwrap_or(bytes.len())
     }
-}
 
-impl<'a> Borrow<[u8]> for CxxStringView<'a> {
-    fn borrow(&self) -> &[u8] {
-        self.as_bytes()
+    /// Splits off the longest prefix of this string view whose bytes all
+    /// satisfy `pred`, returning that prefix and the remainder.
+    ///
+    /// Either view may be empty. This is a building block for hand-written
+    /// tokenizers:
+    ///
+    /// ```
+    /// # use cxx::CxxStringView;
+    /// let sv = CxxStringView::new("1024px");
+    /// let (number, unit) = sv.take_while(|b| b.is_ascii_digit());
+    /// assert_eq!(&number, "1024");
+    /// assert_eq!(&unit, "px");
+    /// ```
+    pub fn take_while<F>(&self, mut pred: F) -> (CxxStringView<'a>, CxxStringView<'a>)
+    where
+        F: FnMut(u8) -> bool,
+    {
+        let bytes = self.as_slice();
+        let mid = bytes.iter().position(|&b| !pred(b)).unwrap_or(bytes.len());
+        let (taken, rest) = bytes.split_at(mid);
+        (CxxStringView::new(taken), CxxStringView::new(rest))
+    }
+
+    /// Returns the first line of this string view.
+    ///
+    /// Lines are split the same way as by [`str::lines`]: the line ends at the
+    /// first `\n`, which is not included, and a `\r` immediately before it is
+    /// stripped too. If there is no `\n` then the whole view is one line.
+    pub fn first_line(&self) -> CxxStringView<'a> {
+        let bytes = self.as_slice();
+        let line = match find_byte(b'\n', bytes) {
+            Some(end) => &bytes[..end],
+            None => bytes,
+        };
+        CxxStringView::new(strip_trailing_cr(line))
+    }
+
+    /// Returns the last line of this string view.
+    ///
+    /// Lines are split the same way as by [`str::lines`], so a final line
+    /// ending is not considered to begin another (empty) line: the last line
+    /// of `"a\nb\n"` is `"b"`. A trailing `\r` is stripped from the result.
+    pub fn last_line(&self) -> CxxStringView<'a> {
+        let mut bytes = self.as_slice();
+        if let Some((b'\n', rest)) = bytes.split_last() {
+            bytes = rest;
+        }
+        let line = match rfind_byte(b'\n', bytes) {
+            Some(start) => &bytes[start + 1..],
+            None => bytes,
+        };
+        CxxStringView::new(strip_trailing_cr(line))
+    }
+
+    /// Returns an iterator over the lines of this string view as raw bytes.
+    ///
+    /// Each line ends at a `\n`, which is not included. Unlike
+    /// [`first_line`][first_line] and [`last_line`][last_line], a preceding
+    /// `\r` is **not** stripped; it is left for the caller to handle. As with
+    /// [`str::lines`], a final `\n` does not produce a trailing empty line.
+    ///
+    /// [first_line]: #method.first_line
+    /// [last_line]: #method.last_line
+    pub fn byte_lines(&self) -> impl Iterator<Item = CxxStringView<'a>> + 'a {
+        let mut rest = self.as_slice();
+        iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let line = match find_byte(b'\n', rest) {
+                Some(end) => {
+                    let line = &rest[..end];
+                    rest = &rest[end + 1..];
+                    line
+                }
+                None => {
+                    let line = rest;
+                    rest = &[];
+                    line
+                }
+            };
+            Some(CxxStringView::new(line))
+        })
+    }
+
+    /// Returns an iterator over the lines of this string view, each paired
+    /// with the byte offset at which it starts.
+    ///
+    /// Lines are split the same way as by [`str::lines`], so a `\r`
+    /// immediately before the `\n` is stripped from the line, but offsets
+    /// always refer to positions in the original view. This is handy for
+    /// turning a byte position into a line and column.
+    pub fn line_offsets(&self) -> impl Iterator<Item = (usize, CxxStringView<'a>)> + 'a {
+        let bytes = self.as_slice();
+        let mut offset = 0;
+        iter::from_fn(move || {
+            let rest = &bytes[offset..];
+            if rest.is_empty() {
+                return None;
+            }
+            let start = offset;
+            let line = match find_byte(b'\n', rest) {
+                Some(end) => {
+                    offset += end + 1;
+                    &rest[..end]
+                }
+                None => {
+                    offset = bytes.len();
+                    rest
+                }
+            };
+            Some((start, CxxStringView::new(strip_trailing_cr(line))))
+        })
+    }
+
+    /// Returns the number of occurrences of `byte` in this string view.
+    ///
+    /// With the `memchr` feature enabled, this uses the vectorized counting
+    /// of the [`memchr`] crate.
+    ///
+    /// [`memchr`]: https://docs.rs/memchr
+    pub fn count_byte(&self, byte: u8) -> usize {
+        count_byte(byte, self.as_bytes())
+    }
+
+    /// Returns the number of lines in this string view, counted the same way
+    /// as the items of [`byte_lines`][byte_lines] but without producing them.
+    ///
+    /// Every `\n` ends one line, and any bytes after the last `\n` form one
+    /// more, unterminated line. So an empty view has 0 lines, `"a\nb"` and
+    /// `"a\nb\n"` both have 2, and `"\n"` has 1.
+    ///
+    /// [byte_lines]: #method.byte_lines
+    pub fn line_count(&self) -> usize {
+        let bytes = self.as_bytes();
+        let newlines = count_byte(b'\n', bytes);
+        match bytes.last() {
+            Some(&b'\n') | None => newlines,
+            Some(_) => newlines + 1,
+        }
+    }
+
+    /// Returns the number of Unicode scalar values in this string view.
+    ///
+    /// Invalid UTF-8 is counted the same way as [`to_string_lossy`][lossy]
+    /// would decode it, with each replacement character counting as one char.
+    /// This requires decoding the entire view, so it is O(n) in the length.
+    ///
+    /// [lossy]: #method.to_string_lossy
+    pub fn chars_count(&self) -> usize {
+        self.lossy_chars().count()
+    }
+
+    /// Returns the number of U+FFFD replacement characters that
+    /// [`to_string_lossy`][lossy] would substitute for invalid UTF-8 in this
+    /// string view, which is 0 exactly when the view is valid UTF-8.
+    ///
+    /// Replacement characters that are already present in the view as valid
+    /// UTF-8 are not counted.
+    ///
+    /// [lossy]: #method.to_string_lossy
+    pub fn lossy_replacement_count(&self) -> usize {
+        Utf8Chunks::new(self.as_slice())
+            .filter(|(_valid, invalid)| !invalid.is_empty())
+            .count()
+    }
+
+    /// Returns the number of columns this string view occupies when displayed
+    /// in a terminal, as determined by the Unicode Standard Annex #11 rules
+    /// implemented in the [`unicode-width`] crate.
+    ///
+    /// East Asian wide and fullwidth characters count as 2 columns, most other
+    /// characters as 1, and control characters as 0. Invalid UTF-8 is decoded
+    /// the same way as by [`to_string_lossy`][lossy], with each replacement
+    /// character counting as 1 column.
+    ///
+    /// [`unicode-width`]: https://docs.rs/unicode-width
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "unicode-width")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "unicode-width")))]
+    pub fn display_width(&self) -> usize {
+        use unicode_width::UnicodeWidthChar;
+        self.lossy_chars().map(|ch| ch.width().unwrap_or(0)).sum()
+    }
+
+    /// Returns an iterator over the extended grapheme clusters of this string
+    /// view, as defined by Unicode Standard Annex #29 and implemented in the
+    /// [`unicode-segmentation`] crate.
+    ///
+    /// Invalid UTF-8 is decoded the same way as by
+    /// [`to_string_lossy`][lossy], and each replacement character is yielded
+    /// as a grapheme of its own.
+    ///
+    /// [`unicode-segmentation`]: https://docs.rs/unicode-segmentation
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "unicode-segmentation")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "unicode-segmentation")))]
+    pub fn graphemes(&self) -> impl Iterator<Item = &'a str> + 'a {
+        use unicode_segmentation::UnicodeSegmentation;
+        Utf8Chunks::new(self.as_slice()).flat_map(|(valid, invalid)| {
+            let replacement = if invalid.is_empty() {
+                None
+            } else {
+                Some("\u{fffd}")
+            };
+            valid.graphemes(true).chain(replacement)
+        })
+    }
+
+    /// Returns the number of grapheme clusters in this string view, counted
+    /// the same way as the items of [`graphemes`][graphemes].
+    ///
+    /// [graphemes]: #method.graphemes
+    #[cfg(feature = "unicode-segmentation")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "unicode-segmentation")))]
+    pub fn grapheme_count(&self) -> usize {
+        self.graphemes().count()
+    }
+
+    /// Checks whether two string views are equal ignoring case, including for
+    /// non-ASCII letters.
+    ///
+    /// Both views are decoded the same way as by [`to_string_lossy`][lossy]
+    /// and compared character by character under Unicode simple case folding,
+    /// the one-to-one mappings of `CaseFolding.txt`. For example `"ÉCOLE"`
+    /// equals `"école"` and final `"ς"` equals `"σ"`, but `"ß"` does not
+    /// equal `"SS"` because that needs full case folding.
+    ///
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "unicode-case")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "unicode-case")))]
+    pub fn eq_unicode_case_fold(&self, other: &CxxStringView) -> bool {
+        self.lossy_chars()
+            .map(simple_case_fold)
+            .eq(other.lossy_chars().map(simple_case_fold))
+    }
+
+    /// Returns an iterator over the maximal runs of valid UTF-8 in this string
+    /// view.
+    ///
+    /// Invalid bytes are skipped and act as separators between runs, so each
+    /// item is the longest non-empty `&str` that fits between invalid
+    /// sequences (or the ends of the view). A view that is entirely valid
+    /// UTF-8 yields itself as a single item, unless it is empty.
+    pub fn utf8_segments(&self) -> impl Iterator<Item = &'a str> + 'a {
+        Utf8Chunks::new(self.as_slice())
+            .map(|(valid, _invalid)| valid)
+            .filter(|valid| !valid.is_empty())
+    }
+
+    // The chars that `to_string_lossy` would produce, without allocating.
+    fn lossy_chars(&self) -> impl Iterator<Item = char> + 'a {
+        Utf8Chunks::new(self.as_slice()).flat_map(|(valid, invalid)| {
+            let replacement = if invalid.is_empty() {
+                None
+            } else {
+                Some(char::REPLACEMENT_CHARACTER)
+            };
+            valid.chars().chain(replacement)
+        })
+    }
+
+    /// Encodes the contents of the C++ string view as UTF-16, after replacing
+    /// any invalid UTF-8 sequences with the U+FFFD [replacement character] as
+    /// in [`to_string_lossy`][lossy].
+    ///
+    /// This is handy for passing text to wide-character APIs such as Win32.
+    ///
+    /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn encode_utf16_lossy(&self) -> Vec<u16> {
+        self.to_string_lossy().encode_utf16().collect()
+    }
+
+    /// Returns a new buffer in which each byte of this string view has been
+    /// transformed by `f`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn map_bytes<F>(&self, mut f: F) -> Vec<u8>
+    where
+        F: FnMut(u8) -> u8,
+    {
+        self.as_bytes().iter().map(|&b| f(b)).collect()
+    }
+
+    /// Returns a new C++ string holding a copy of this string view.
+    ///
+    /// Like [`to_ascii_lowercase_cxx_string`][lower], the copy is made
+    /// directly into the C++ heap without an intermediate Rust allocation.
+    ///
+    /// [lower]: #method.to_ascii_lowercase_cxx_string
+    pub fn to_cxx_string(&self) -> UniquePtr<CxxString> {
+        string::new_unique(self.as_bytes())
+    }
+
+    /// Copies the bytes of this string view into a new [`CxxStringViewBuf`],
+    /// which owns them and so is not tied to the lifetime of the view.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_owned_buf(&self) -> CxxStringViewBuf {
+        CxxStringViewBuf {
+            bytes: self.as_bytes().to_vec(),
+        }
+    }
+
+    /// Returns a new C++ string holding a copy of this string view with every
+    /// ASCII letter converted to lowercase.
+    ///
+    /// Non-ASCII bytes are copied unchanged. The result is built directly on
+    /// the C++ heap, for handing back to C++ code, without an intermediate
+    /// Rust allocation.
+    pub fn to_ascii_lowercase_cxx_string(&self) -> UniquePtr<CxxString> {
+        let mut string = string::new_unique(&[]);
+        let mut pinned = string.pin_mut();
+        pinned.as_mut().reserve(self.len());
+        let mut buf = [0u8; 256];
+        for chunk in self.as_bytes().chunks(buf.len()) {
+            let lowercase = &mut buf[..chunk.len()];
+            lowercase.copy_from_slice(chunk);
+            lowercase.make_ascii_lowercase();
+            pinned.as_mut().push_bytes(lowercase);
+        }
+        string
+    }
+
+    /// Returns a copy of this string view in which the first ASCII letter of
+    /// each word is converted to uppercase, where words are separated by ASCII
+    /// whitespace.
+    ///
+    /// Characters before that letter, such as punctuation, digits or
+    /// non-ASCII letters, do not count as its start, so `"(hello)"` becomes
+    /// `"(Hello)"` and `"1st"` becomes `"1St"`. All other characters are left
+    /// unchanged, and invalid UTF-8 sequences
+    /// are replaced with the U+FFFD [replacement character] as in
+    /// [`to_string_lossy`][lossy].
+    ///
+    /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_ascii_titlecase(&self) -> String {
+        let mut title = String::with_capacity(self.len());
+        let mut capitalized_this_word = false;
+        for ch in self.lossy_chars() {
+            if ch.is_ascii_whitespace() {
+                capitalized_this_word = false;
+                title.push(ch);
+            } else if !capitalized_this_word && ch.is_ascii_alphabetic() {
+                capitalized_this_word = true;
+                title.push(ch.to_ascii_uppercase());
+            } else {
+                title.push(ch);
+            }
+        }
+        title
+    }
+
+    /// Returns a copy of this string view with every `\r\n` and every lone
+    /// `\r` replaced by `\n`.
+    ///
+    /// Invalid UTF-8 sequences are replaced with the U+FFFD [replacement
+    /// character] as in [`to_string_lossy`][lossy].
+    ///
+    /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn normalize_newlines(&self) -> String {
+        let mut normalized = String::with_capacity(self.len());
+        let mut chars = self.lossy_chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch == '\r' {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                normalized.push('\n');
+            } else {
+                normalized.push(ch);
+            }
+        }
+        normalized
+    }
+
+    /// Returns an iterator over sub-views of this string view separated by
+    /// `delimiter`, where each item includes its terminating delimiter byte.
+    ///
+    /// Like [`str::split_inclusive`], if the view ends with the delimiter
+    /// there is no trailing empty item, and if the last segment has no
+    /// delimiter it is yielded as is.
+    pub fn split_inclusive(&self, delimiter: u8) -> impl Iterator<Item = CxxStringView<'a>> + 'a {
+        let mut rest = self.as_slice();
+        iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let end = match find_byte(delimiter, rest) {
+                Some(i) => i + 1,
+                None => rest.len(),
+            };
+            let (segment, tail) = rest.split_at(end);
+            rest = tail;
+            Some(CxxStringView::new(segment))
+        })
+    }
+
+    /// Splits this string view on `delimiter` into exactly `N` sub-views.
+    ///
+    /// Returns `None` if the view does not consist of exactly `N` fields,
+    /// i.e. if it contains any number of delimiters other than `N - 1`. This
+    /// makes it convenient to destructure records with a fixed layout:
+    ///
+    /// ```
+    /// # use cxx::CxxStringView;
+    /// # fn parse(sv: CxxStringView) -> Option<()> {
+    /// let [name, version, license] = sv.split_n(b',')?;
+    /// # let _ = (name, version, license);
+    /// # Some(())
+    /// # }
+    /// # parse(CxxStringView::new("cxx,1.0,MIT")).unwrap();
+    /// ```
+    pub fn split_n<const N: usize>(&self, delimiter: u8) -> Option<[CxxStringView<'a>; N]> {
+        if N == 0 {
+            return None;
+        }
+        let mut fields: [&'a [u8]; N] = [&[]; N];
+        let mut rest = self.as_slice();
+        for field in fields[..N - 1].iter_mut() {
+            let end = find_byte(delimiter, rest)?;
+            *field = &rest[..end];
+            rest = &rest[end + 1..];
+        }
+        if rest.contains(&delimiter) {
+            return None;
+        }
+        fields[N - 1] = rest;
+
+        // Safety: an array of MaybeUninit does not require initialization.
+        let mut views: [MaybeUninit<CxxStringView<'a>>; N] =
+            unsafe { MaybeUninit::uninit().assume_init() };
+        for (view, field) in views.iter_mut().zip(fields.iter()) {
+            *view = MaybeUninit::new(CxxStringView::new(*field));
+        }
+        // Safety: every element was initialized above, and MaybeUninit<T> has
+        // the same layout as T.
+        Some(unsafe { views.as_ptr().cast::<[CxxStringView<'a>; N]>().read() })
+    }
+
+    /// Copies the contents of this string view into a new heap allocation and
+    /// returns a view of the copy that lives for the rest of the program.
+    ///
+    /// **The copy is deliberately leaked** and never freed, similar to
+    /// [`Box::leak`]. This is intended for data that must stay alive until the
+    /// process exits, such as strings handed to a plugin host; calling it
+    /// repeatedly will grow memory usage without bound.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn into_static_copy(&self) -> CxxStringView<'static> {
+        let copy: Box<[u8]> = Box::from(self.as_bytes());
+        CxxStringView::new(Box::leak(copy))
+    }
+
+    /// Formats the bytes of this string view as lowercase hexadecimal, two
+    /// digits per byte with no separators.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_hex(&self) -> String {
+        let bytes = self.as_bytes();
+        let mut hex = String::with_capacity(bytes.len() * 2);
+        for &b in bytes {
+            push_hex_byte(&mut hex, b);
+        }
+        hex
+    }
+
+    /// Formats the bytes of this string view as a multi-line hex dump, in the
+    /// format of `hexdump -C -v`.
+    ///
+    /// Each line shows the offset of its first byte, up to 16 bytes in hex in
+    /// two groups of 8, and the same bytes as ASCII with non-printable bytes
+    /// shown as `.`. A final line holds the total length. As with `-v`,
+    /// repeated lines are printed in full rather than collapsed into `*`. An
+    /// empty view produces an empty string.
+    ///
+    /// ```text
+    /// 00000000  68 65 6c 6c 6f 0a                                 |hello.|
+    /// 00000006
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_hex_dump(&self) -> String {
+        let mut dump = String::new();
+        for (i, line) in self.as_bytes().chunks(16).enumerate() {
+            let _ = write!(dump, "{:08x} ", i * 16);
+            for j in 0..16 {
+                if j == 8 {
+                    dump.push(' ');
+                }
+                match line.get(j) {
+                    Some(&b) => {
+                        dump.push(' ');
+                        push_hex_byte(&mut dump, b);
+                    }
+                    None => dump.push_str("   "),
+                }
+            }
+            dump.push_str("  |");
+            for &b in line {
+                let printable = b.is_ascii_graphic() || b == b' ';
+                dump.push(if printable { b as char } else { '.' });
+            }
+            dump.push_str("|\n");
+        }
+        if !self.is_empty() {
+            let _ = writeln!(dump, "{:08x}", self.len());
+        }
+        dump
+    }
+
+    /// Formats the string view for diagnostics such as log messages.
+    ///
+    /// Returns the text itself if the view is valid UTF-8, and otherwise a
+    /// compact hex rendering of its bytes like `<3 bytes: 61 ff 62>`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_display_string(&self) -> String {
+        let bytes = self.as_bytes();
+        if let Ok(s) = str::from_utf8(bytes) {
+            return String::from(s);
+        }
+        let mut display = String::with_capacity(16 + bytes.len() * 3);
+        let _ = write!(display, "<{} bytes:", bytes.len());
+        for &b in bytes {
+            display.push(' ');
+            push_hex_byte(&mut display, b);
+        }
+        display.push('>');
+        display
+    }
+
+    /// Formats the string view as a double-quoted JSON string literal.
+    ///
+    /// `"` and `\` are backslash-escaped, control characters use the short
+    /// escapes `\b`, `\f`, `\n`, `\r` and `\t` where JSON has them and
+    /// `\u00XX` otherwise, and everything else is copied as is. Invalid UTF-8
+    /// is first replaced the same way as by [`to_string_lossy`][lossy].
+    ///
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_json_string(&self) -> String {
+        let mut json = String::with_capacity(self.len() + 2);
+        json.push('"');
+        for ch in self.lossy_chars() {
+            match ch {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                '\u{8}' => json.push_str("\\b"),
+                '\u{c}' => json.push_str("\\f"),
+                '\n' => json.push_str("\\n"),
+                '\r' => json.push_str("\\r"),
+                '\t' => json.push_str("\\t"),
+                '\0'..='\u{1f}' => {
+                    let _ = write!(json, "\\u{:04x}", ch as u32);
+                }
+                _ => json.push(ch),
+            }
+        }
+        json.push('"');
+        json
+    }
+
+    /// Appends the contents of the C++ string view onto the end of `buf`,
+    /// replacing any invalid UTF-8 sequences with the U+FFFD [replacement
+    /// character] the same way as [`to_string_lossy`][lossy].
+    ///
+    /// Unlike `to_string_lossy`, this never allocates a temporary string, so
+    /// it's the cheaper choice when converting many views into one buffer.
+    ///
+    /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    /// [lossy]: #method.to_string_lossy
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_string_lossy_into(&self, buf: &mut String) {
+        let bytes = self.as_bytes();
+        buf.reserve(bytes.len());
+        let _ = write!(buf, "{}", crate::fmt::display(|f| lossy::display(bytes, f)));
+    }
+
+    /// Converts the C++ string view into an OS string.
+    ///
+    /// On Unix an `OsStr` is an arbitrary sequence of bytes, so this always
+    /// borrows the view's contents as they are. On other platforms, including
+    /// Windows, an `OsStr` must be well-formed Unicode, so if the view is not
+    /// valid UTF-8 the invalid sequences are replaced with the U+FFFD
+    /// [replacement character] and an owned `OsString` is returned.
+    ///
+    /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn to_os_str_lossy(&self) -> Cow<'a, OsStr> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Cow::Borrowed(OsStr::from_bytes(self.as_slice()))
+        }
+        #[cfg(not(unix))]
+        {
+            match String::from_utf8_lossy(self.as_slice()) {
+                Cow::Borrowed(s) => Cow::Borrowed(OsStr::new(s)),
+                Cow::Owned(s) => Cow::Owned(std::ffi::OsString::from(s)),
+            }
+        }
+    }
+
+    /// Copies the contents of this string view into an owned path.
+    ///
+    /// Unix paths are arbitrary sequences of bytes, so the bytes are copied
+    /// exactly, whether or not they are valid UTF-8. The resulting `PathBuf`
+    /// does not borrow from the view.
+    #[cfg(all(unix, feature = "std"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(unix, feature = "std"))))]
+    pub fn to_path_buf(&self) -> PathBuf {
+        use std::os::unix::ffi::OsStringExt;
+        PathBuf::from(OsString::from_vec(self.as_bytes().to_vec()))
+    }
+
+    /// Returns a reader over the contents of this string view, for passing to
+    /// code that consumes an [`std::io::Read`]. The bytes are not copied.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn reader(&self) -> impl Read + 'a {
+        Cursor::new(self.as_slice())
+    }
+
+    /// Feeds the contents of this string view into `state` in pieces of at
+    /// most `chunk_size` bytes.
+    ///
+    /// The length is written first, exactly as by the `Hash` impl, so for
+    /// hashers that treat consecutive writes as one stream (such as the
+    /// standard library's `DefaultHasher`) the result is the same as from
+    /// [`Hash::hash`]. Chunking bounds the size of each individual write for
+    /// hashers that are sensitive to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn hash_into<H: Hasher>(&self, state: &mut H, chunk_size: usize) {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let bytes = self.as_bytes();
+        state.write_usize(bytes.len());
+        for chunk in bytes.chunks(chunk_size) {
+            state.write(chunk);
+        }
+    }
+
+    /// Returns the byte index of the `n`th non-overlapping occurrence of
+    /// `needle`, counting from zero, or `None` if there are not that many.
+    ///
+    /// So `find_nth(needle, 0)` finds the first occurrence. After each match
+    /// the search resumes at the end of that match. An empty needle matches
+    /// at every index from 0 up to and including `len()`.
+    pub fn find_nth<T>(&self, needle: &T, n: usize) -> Option<usize>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = self.as_bytes();
+        let needle = needle.as_ref();
+        let mut start = 0;
+        let mut remaining = n;
+        loop {
+            let index = start + find_subslice(haystack.get(start..)?, needle)?;
+            if remaining == 0 {
+                return Some(index);
+            }
+            remaining -= 1;
+            start = index + needle.len().max(1);
+        }
+    }
+
+    /// Returns an iterator over the byte ranges of each non-overlapping
+    /// occurrence of `needle`, from left to right.
+    ///
+    /// Matches are found the same way as by [`find_nth`][find_nth], so every
+    /// range has length `needle.len()` and an empty needle yields an empty
+    /// range at every index from 0 up to and including `len()`.
+    ///
+    /// [find_nth]: #method.find_nth
+    pub fn match_ranges<T>(&self, needle: &'a T) -> impl Iterator<Item = Range<usize>> + 'a
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = self.as_slice();
+        let needle = needle.as_ref();
+        let mut start = 0;
+        iter::from_fn(move || {
+            let index = start + find_subslice(haystack.get(start..)?, needle)?;
+            start = index + needle.len().max(1);
+            Some(index..index + needle.len())
+        })
+    }
+
+    /// Returns the byte index of the first byte matching `pattern`, or `None`
+    /// if there is no match.
+    ///
+    /// With the `memchr` feature enabled, single bytes and small byte sets are
+    /// searched using the vectorized routines of the [`memchr`] crate.
+    ///
+    /// [`memchr`]: https://docs.rs/memchr
+    pub fn find<'p, F, P>(&self, pattern: P) -> Option<usize>
+    where
+        P: Into<ViewPattern<'p, F>>,
+        F: FnMut(u8) -> bool,
+    {
+        let mut pattern = pattern.into();
+        pattern.find_in(self.as_bytes())
+    }
+
+    /// Returns an iterator over the sub-views of this string view separated by
+    /// bytes matching `pattern`.
+    ///
+    /// Like [`str::split`], consecutive matches and matches at either end
+    /// produce empty items, so there is always one more item than there are
+    /// matches. An empty view produces a single empty item.
+    pub fn split<F, P>(&self, pattern: P) -> impl Iterator<Item = CxxStringView<'a>> + 'a
+    where
+        P: Into<ViewPattern<'a, F>>,
+        F: FnMut(u8) -> bool + 'a,
+    {
+        let mut pattern = pattern.into();
+        let mut rest = Some(self.as_slice());
+        iter::from_fn(move || {
+            let bytes = rest?;
+            match pattern.find_in(bytes) {
+                Some(i) => {
+                    rest = Some(&bytes[i + 1..]);
+                    Some(CxxStringView::new(&bytes[..i]))
+                }
+                None => {
+                    rest = None;
+                    Some(CxxStringView::new(bytes))
+                }
+            }
+        })
+    }
+
+    /// Returns an iterator over sub-views separated by occurrences of the whole
+    /// multi-byte `delimiter`, like `str::split` with a `&str` pattern.
+    ///
+    /// Occurrences are found the same way as by [`find_nth`][find_nth], left
+    /// to right without overlapping, so splitting `"aaa"` on `"aa"` yields
+    /// `""` and `"a"`. An empty delimiter matches at every byte index from 0
+    /// up to and including `len()`, so splitting `"ab"` on `""` yields `""`,
+    /// `"a"`, `"b"` and `""`.
+    ///
+    /// [find_nth]: #method.find_nth
+    pub fn split_str<T>(&self, delimiter: &'a T) -> impl Iterator<Item = CxxStringView<'a>> + 'a
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        split_subslice(self.as_slice(), delimiter.as_ref(), true)
+    }
+
+    /// Returns an iterator over sub-views terminated by occurrences of the
+    /// whole multi-byte `delimiter`, like `str::split_terminator` with a `&str`
+    /// pattern.
+    ///
+    /// This is the same as [`split_str`][split_str], except that the last
+    /// item is skipped if it is empty. So if the view ends with the delimiter
+    /// no empty item is yielded after it, and an empty view split on a
+    /// non-empty delimiter yields nothing at all. Only one trailing delimiter
+    /// is treated as a terminator, so `"a;;"` split on `";"` yields `"a"` and
+    /// `""`.
+    ///
+    /// [split_str]: #method.split_str
+    pub fn split_terminator_str<T>(
+        &self,
+        delimiter: &'a T,
+    ) -> impl Iterator<Item = CxxStringView<'a>> + 'a
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        split_subslice(self.as_slice(), delimiter.as_ref(), false)
+    }
+
+    /// Splits the string view at the `n`th occurrence of `delimiter`,
+    /// counting from one, returning the first `n` fields and the rest.
+    ///
+    /// The `n`th delimiter itself is in neither part, so splitting `"a,b,c,d"`
+    /// after the 2nd `,` gives `"a,b"` and `"c,d"`. Returns `None` if the view
+    /// contains fewer than `n` delimiters. For `n == 0` the first part is
+    /// empty and the second is the whole view.
+    pub fn split_after_nth(
+        &self,
+        delimiter: u8,
+        n: usize,
+    ) -> Option<(CxxStringView<'a>, CxxStringView<'a>)> {
+        let bytes = self.as_slice();
+        if n == 0 {
+            return Some((CxxStringView::new(&bytes[..0]), CxxStringView::new(bytes)));
+        }
+        let mut index = 0;
+        for i in 0..n {
+            let start = if i == 0 { 0 } else { index + 1 };
+            index = start + find_byte(delimiter, &bytes[start..])?;
+        }
+        let before = CxxStringView::new(&bytes[..index]);
+        let after = CxxStringView::new(&bytes[index + 1..]);
+        Some((before, after))
+    }
+
+    /// Splits the string view on the first occurrence of the multi-byte
+    /// `delimiter`, returning the parts before and after it, or `None` if the
+    /// delimiter does not occur.
+    ///
+    /// Neither part includes the delimiter itself. The first occurrence is the
+    /// one found by [`find_nth`][find_nth] with `n == 0`, so like
+    /// `str::split_once`, an empty delimiter matches at the start, giving an
+    /// empty first part.
+    ///
+    /// [find_nth]: #method.find_nth
+    pub fn split_once_str<T>(&self, delimiter: &T) -> Option<(CxxStringView<'a>, CxxStringView<'a>)>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let bytes = self.as_slice();
+        let delimiter = delimiter.as_ref();
+        let i = find_subslice(bytes, delimiter)?;
+        let before = CxxStringView::new(&bytes[..i]);
+        let after = CxxStringView::new(&bytes[i + delimiter.len()..]);
+        Some((before, after))
+    }
+
+    /// Returns an iterator over the sub-views separated by `delimiter`, each
+    /// paired with the byte offset at which it starts in this view.
+    ///
+    /// The sub-views are the same as those yielded by [`split`][split] with
+    /// a single byte, so consecutive delimiters produce empty items, each
+    /// with its own offset.
+    ///
+    /// [split]: #method.split
+    pub fn split_indices(
+        &self,
+        delimiter: u8,
+    ) -> impl Iterator<Item = (usize, CxxStringView<'a>)> + 'a {
+        let bytes = self.as_slice();
+        let mut offset = Some(0);
+        iter::from_fn(move || {
+            let start = offset?;
+            let rest = &bytes[start..];
+            let end = match find_byte(delimiter, rest) {
+                Some(i) => {
+                    offset = Some(start + i + 1);
+                    i
+                }
+                None => {
+                    offset = None;
+                    rest.len()
+                }
+            };
+            Some((start, CxxStringView::new(&rest[..end])))
+        })
+    }
+
+    /// Returns an iterator over the fields of this string view, treated as a
+    /// single comma-separated record in the style of RFC 4180.
+    ///
+    /// A field that begins with `"` is quoted: it extends to the next `"`
+    /// that is not part of a doubled `""`, may contain commas and newlines,
+    /// and has its surrounding quotes removed and each `""` unescaped to `"`.
+    /// Fields are borrowed from the view whenever no unescaping is needed. Any
+    /// bytes between a closing quote and the next comma are kept as they are,
+    /// and an unterminated quoted field runs to the end of the view.
+    ///
+    /// Like [`split`][split], an empty view produces one empty field and a
+    /// trailing comma produces a trailing empty field. This does not split
+    /// multiple records apart; it is not a full CSV parser.
+    ///
+    /// [split]: #method.split
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn csv_fields(&self) -> impl Iterator<Item = Cow<'a, [u8]>> + 'a {
+        let mut rest = Some(self.as_slice());
+        iter::from_fn(move || {
+            let bytes = rest?;
+            if let Some((b'"', quoted)) = bytes.split_first() {
+                let (field, tail) = parse_quoted_csv_field(quoted);
+                rest = tail;
+                return Some(field);
+            }
+            match find_byte(b',', bytes) {
+                Some(i) => {
+                    rest = Some(&bytes[i + 1..]);
+                    Some(Cow::Borrowed(&bytes[..i]))
+                }
+                None => {
+                    rest = None;
+                    Some(Cow::Borrowed(bytes))
+                }
+            }
+        })
+    }
+
+    /// Returns an iterator over the non-empty sub-views of this string view
+    /// separated by `delimiter`.
+    ///
+    /// Unlike [`split`][split], leading, trailing, and consecutive delimiters
+    /// never produce empty items, the same way that
+    /// [`split_whitespace`][split_whitespace] treats whitespace.
+    ///
+    /// [split]: #method.split
+    /// [split_whitespace]: #method.split_whitespace
+    pub fn split_non_empty(&self, delimiter: u8) -> impl Iterator<Item = CxxStringView<'a>> + 'a {
+        self.as_slice()
+            .split(move |&b| b == delimiter)
+            .filter(|segment| !segment.is_empty())
+            .map(CxxStringView::new)
+    }
+
+    /// Returns the sub-view with all leading and trailing bytes that match
+    /// `pattern` removed.
+    pub fn trim_matches<'p, F, P>(&self, pattern: P) -> CxxStringView<'a>
+    where
+        P: Into<ViewPattern<'p, F>>,
+        F: FnMut(u8) -> bool,
+    {
+        let mut pattern = pattern.into();
+        let bytes = self.as_slice();
+        let start = match bytes.iter().position(|&b| !pattern.matches(b)) {
+            Some(start) => start,
+            None => return CxxStringView::new(&bytes[bytes.len()..]),
+        };
+        let end = bytes.iter().rposition(|&b| !pattern.matches(b)).unwrap() + 1;
+        CxxStringView::new(&bytes[start..end])
+    }
+
+    /// Returns an iterator over the non-empty sub-views of this string view,
+    /// separated by any amount of ASCII whitespace.
+    ///
+    /// Like [`str::split_whitespace`], leading, trailing, and consecutive
+    /// whitespace never produce empty items. Each item borrows from the same
+    /// underlying data as `self`.
+    pub fn split_whitespace(&self) -> impl Iterator<Item = CxxStringView<'a>> + 'a {
+        self.as_slice()
+            .split(u8::is_ascii_whitespace)
+            .filter(|segment| !segment.is_empty())
+            .map(CxxStringView::new)
+    }
+}
+
+// Splits bytes into alternating runs of valid UTF-8 and the single invalid
+// sequence that follows each run, which String::from_utf8_lossy would replace
+// with one U+FFFD. The invalid part is empty only for the final chunk.
+struct Utf8Chunks<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Utf8Chunks<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Utf8Chunks { bytes }
+    }
+}
+
+impl<'a> Iterator for Utf8Chunks<'a> {
+    type Item = (&'a str, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        match str::from_utf8(self.bytes) {
+            Ok(valid) => {
+                self.bytes = &[];
+                Some((valid, &[]))
+            }
+            Err(utf8_error) => {
+                let valid_up_to = utf8_error.valid_up_to();
+                let end_of_broken = match utf8_error.error_len() {
+                    Some(error_len) => valid_up_to + error_len,
+                    None => self.bytes.len(),
+                };
+                let valid = unsafe { str::from_utf8_unchecked(&self.bytes[..valid_up_to]) };
+                let invalid = &self.bytes[valid_up_to..end_of_broken];
+                self.bytes = &self.bytes[end_of_broken..];
+                Some((valid, invalid))
+            }
+        }
+    }
+}
+
+// Parses a quoted CSV field whose opening quote has already been consumed.
+// Returns the unescaped field, and what follows the comma that ends it, if
+// any.
+#[cfg(feature = "alloc")]
+fn parse_quoted_csv_field(bytes: &[u8]) -> (Cow<[u8]>, Option<&[u8]>) {
+    let mut field = Cow::Borrowed(&[][..]);
+    let mut start = 0;
+    loop {
+        let quote = match find_byte(b'"', &bytes[start..]) {
+            Some(i) => start + i,
+            None => {
+                append_csv_segment(&mut field, &bytes[start..]);
+                return (field, None);
+            }
+        };
+        if bytes.get(quote + 1) == Some(&b'"') {
+            append_csv_segment(&mut field, &bytes[start..=quote]);
+            start = quote + 2;
+            continue;
+        }
+        append_csv_segment(&mut field, &bytes[start..quote]);
+        let after_quote = &bytes[quote + 1..];
+        return match find_byte(b',', after_quote) {
+            Some(i) => {
+                append_csv_segment(&mut field, &after_quote[..i]);
+                (field, Some(&after_quote[i + 1..]))
+            }
+            None => {
+                append_csv_segment(&mut field, after_quote);
+                (field, None)
+            }
+        };
+    }
+}
+
+// Borrows the segment if nothing has been collected yet, and only allocates
+// once a second non-contiguous segment needs to be appended.
+#[cfg(feature = "alloc")]
+fn append_csv_segment<'a>(field: &mut Cow<'a, [u8]>, segment: &'a [u8]) {
+    if field.is_empty() {
+        *field = Cow::Borrowed(segment);
+    } else if !segment.is_empty() {
+        field.to_mut().extend_from_slice(segment);
+    }
+}
+
+// Byte and substring searches are accelerated by the `memchr` crate when its
+// feature is enabled, and otherwise fall back to a plain scan.
+
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    return memchr::memchr(needle, haystack);
+    #[cfg(not(feature = "memchr"))]
+    return haystack.iter().position(|&b| b == needle);
+}
+
+fn rfind_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    return memchr::memrchr(needle, haystack);
+    #[cfg(not(feature = "memchr"))]
+    return haystack.iter().rposition(|&b| b == needle);
+}
+
+fn count_byte(needle: u8, haystack: &[u8]) -> usize {
+    #[cfg(feature = "memchr")]
+    return memchr::memchr_iter(needle, haystack).count();
+    #[cfg(not(feature = "memchr"))]
+    return haystack.iter().filter(|&&b| b == needle).count();
+}
+
+// Splits `bytes` on each non-overlapping occurrence of `delimiter`, found the
+// same way as by CxxStringView::find_nth, so an empty delimiter matches at
+// every index. Unless `allow_trailing_empty`, an empty last item is skipped.
+fn split_subslice<'a>(
+    bytes: &'a [u8],
+    delimiter: &'a [u8],
+    allow_trailing_empty: bool,
+) -> impl Iterator<Item = CxxStringView<'a>> + 'a {
+    let mut start = 0;
+    let mut search = 0;
+    let mut finished = false;
+    iter::from_fn(move || {
+        if finished {
+            return None;
+        }
+        let found = bytes
+            .get(search..)
+            .and_then(|haystack| find_subslice(haystack, delimiter));
+        match found {
+            Some(i) => {
+                let index = search + i;
+                let item = &bytes[start..index];
+                start = index + delimiter.len();
+                search = index + delimiter.len().max(1);
+                Some(CxxStringView::new(item))
+            }
+            None => {
+                finished = true;
+                if !allow_trailing_empty && start == bytes.len() {
+                    return None;
+                }
+                Some(CxxStringView::new(&bytes[start..]))
+            }
+        }
+    })
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    return memchr::memmem::find(haystack, needle);
+    #[cfg(not(feature = "memchr"))]
+    {
+        if needle.is_empty() {
+            return Some(0);
+        }
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+}
+
+// Maps a char to a representative of its class under Unicode simple case
+// folding, so that two chars fold to the same char exactly when this returns
+// the same value for both. That is the lowercase mapping except for the chars
+// below, whose simple case folding is not their lowercase.
+#[cfg(feature = "unicode-case")]
+fn simple_case_fold(c: char) -> char {
+    match c {
+        '\u{b5}' => '\u{3bc}',
+        '\u{130}' => '\u{130}',
+        '\u{17f}' => 's',
+        '\u{345}' | '\u{1fbe}' => '\u{3b9}',
+        '\u{3c2}' => '\u{3c3}',
+        '\u{3d0}' => '\u{3b2}',
+        '\u{3d1}' => '\u{3b8}',
+        '\u{3d5}' => '\u{3c6}',
+        '\u{3d6}' => '\u{3c0}',
+        '\u{3f0}' => '\u{3ba}',
+        '\u{3f1}' => '\u{3c1}',
+        '\u{3f5}' => '\u{3b5}',
+        '\u{1c80}' => '\u{432}',
+        '\u{1c81}' => '\u{434}',
+        '\u{1c82}' => '\u{43e}',
+        '\u{1c83}' => '\u{441}',
+        '\u{1c84}' | '\u{1c85}' => '\u{442}',
+        '\u{1c86}' => '\u{44a}',
+        '\u{1c87}' => '\u{463}',
+        '\u{1c88}' => '\u{a64b}',
+        '\u{1e9b}' => '\u{1e61}',
+        '\u{1fd3}' => '\u{390}',
+        '\u{1fe3}' => '\u{3b0}',
+        '\u{fb05}' => '\u{fb06}',
+        _ => {
+            let mut lowercase = c.to_lowercase();
+            match (lowercase.next(), lowercase.next()) {
+                (Some(lower), None) => lower,
+                _ => c,
+            }
+        }
+    }
+}
+
+fn check_alignment<T>(ptr: *const u8) -> bool {
+    ptr as usize % mem::align_of::<T>() == 0
+}
+
+fn is_utf8_continuation(b: u8) -> bool {
+    b & 0b1100_0000 == 0b1000_0000
+}
+
+fn trim_ascii_whitespace(bytes: &[u8]) -> &[u8] {
+    trim_ascii_whitespace_end(trim_ascii_whitespace_start(bytes))
+}
+
+fn trim_ascii_whitespace_start(mut bytes: &[u8]) -> &[u8] {
+    while let Some((first, rest)) = bytes.split_first() {
+        if !first.is_ascii_whitespace() {
+            break;
+        }
+        bytes = rest;
+    }
+    bytes
+}
+
+fn trim_ascii_whitespace_end(mut bytes: &[u8]) -> &[u8] {
+    while let Some((last, rest)) = bytes.split_last() {
+        if !last.is_ascii_whitespace() {
+            break;
+        }
+        bytes = rest;
+    }
+    bytes
+}
+
+#[cfg(feature = "alloc")]
+fn push_hex_byte(out: &mut String, b: u8) {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+    out.push(HEX_DIGITS[(b >> 4) as usize] as char);
+    out.push(HEX_DIGITS[(b & 0xf) as usize] as char);
+}
+
+fn strip_trailing_cr(line: &[u8]) -> &[u8] {
+    match line.split_last() {
+        Some((b'\r', rest)) => rest,
+        _ => line,
+    }
+}
+
+impl<'a> Display for CxxStringView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self.to_string_lossy().as_ref(), f)
+    }
+}
+
+impl<'a> Debug for CxxStringView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self.to_string_lossy().as_ref(), f)
+    }
+}
+
+/// Converts the view to an owned `String`, replacing any invalid UTF-8
+/// sequences with the U+FFFD replacement character as in
+/// [`CxxStringView::to_string_lossy`]. Use [`CxxStringView::to_str`] instead
+/// to reject invalid UTF-8.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'a> From<CxxStringView<'a>> for String {
+    fn from(view: CxxStringView<'a>) -> Self {
+        view.to_string_lossy().into_owned()
+    }
+}
+
+/// Converts the view to an owned `String`, replacing any invalid UTF-8
+/// sequences with the U+FFFD replacement character as in
+/// [`CxxStringView::to_string_lossy`]. Use [`CxxStringView::to_str`] instead
+/// to reject invalid UTF-8.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'a, 'b> From<&'b CxxStringView<'a>> for String {
+    fn from(view: &'b CxxStringView<'a>) -> Self {
+        view.to_string_lossy().into_owned()
+    }
+}
+
+/// Views the entire contents of a C++ string, the same as
+/// [`CxxString::to_string_view`]. This cannot fail.
+impl<'a> From<&'a CxxString> for CxxStringView<'a> {
+    fn from(string: &'a CxxString) -> Self {
+        string.to_string_view()
+    }
+}
+
+impl<'a> PartialEq for CxxStringView<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<CxxStringView<'a>> for str {
+    fn eq(&self, other: &CxxStringView<'a>) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<str> for CxxStringView<'a> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<CxxStringView<'a>> for CxxString {
+    fn eq(&self, other: &CxxStringView<'a>) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<CxxString> for CxxStringView<'a> {
+    fn eq(&self, other: &CxxString) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+/// Compares the bytes of the view against the bytes of the C string, not
+/// including the C string's trailing NUL terminator.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl<'a> PartialEq<CStr> for CxxStringView<'a> {
+    fn eq(&self, other: &CStr) -> bool {
+        self.as_bytes() == other.to_bytes()
+    }
+}
+
+/// Compares the bytes of the C string, not including its trailing NUL
+/// terminator, against the bytes of the view.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl<'a> PartialEq<CxxStringView<'a>> for CStr {
+    fn eq(&self, other: &CxxStringView<'a>) -> bool {
+        self.to_bytes() == other.as_bytes()
+    }
+}
+
+impl<'a> Eq for CxxStringView<'a> {}
+
+impl<'a> PartialOrd for CxxStringView<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_bytes().partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> Ord for CxxStringView<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<'a> Hash for CxxStringView<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl<'a> AsRef<[u8]> for CxxStringView<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> Borrow<[u8]> for CxxStringView<'a> {
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Appends `views` onto the end of a C++ string, with `separator` between
+/// each consecutive pair.
+///
+/// The total length is computed up front so that the C++ string reserves its
+/// capacity once, rather than reallocating as each view is appended.
+///
+/// # Example
+///
+/// ```
+/// use cxx::{let_cxx_string, CxxStringView};
+///
+/// let views = [CxxStringView::new("a"), CxxStringView::new("b")];
+/// let_cxx_string!(s = "");
+/// cxx::join_into_cxx_string(s.as_mut(), &views, CxxStringView::new(", "));
+/// assert_eq!(s.to_str(), Ok("a, b"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the new capacity overflows usize.
+pub fn join_into_cxx_string(
+    mut out: Pin<&mut CxxString>,
+    views: &[CxxStringView],
+    separator: CxxStringView,
+) {
+    let separators = views.len().saturating_sub(1);
+    let additional = views
+        .iter()
+        .fold(separator.len().checked_mul(separators), |total, view| {
+            total?.checked_add(view.len())
+        })
+        .expect("CxxString capacity overflow");
+    out.as_mut().reserve(additional);
+    for (i, view) in views.iter().enumerate() {
+        if i > 0 {
+            out.as_mut().push_bytes(separator.as_bytes());
+        }
+        out.as_mut().push_bytes(view.as_bytes());
+    }
+}
+
+/// Concatenates `views` into a newly allocated buffer.
+///
+/// The total length is computed with overflow checking before anything is
+/// allocated, and `None` is returned if it does not fit in `usize`. The
+/// buffer is then allocated once with exactly that capacity.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn try_concat_views(views: &[CxxStringView]) -> Option<Vec<u8>> {
+    let total = checked_total_len(views.iter().map(|view| view.len()))?;
+    let mut concat = Vec::with_capacity(total);
+    for view in views {
+        concat.extend_from_slice(view.as_bytes());
+    }
+    Some(concat)
+}
+
+// Sums the lengths of the views to concatenate, or returns None if the total
+// does not fit in usize.
+#[cfg(feature = "alloc")]
+fn checked_total_len(lens: impl IntoIterator<Item = usize>) -> Option<usize> {
+    lens.into_iter()
+        .try_fold(0usize, |total, len| total.checked_add(len))
+}
+
+/// Concatenates string views into a buffer that is reused from one
+/// concatenation to the next.
+///
+/// Call [`push`][push] for each view and then [`finish`][finish] to get the
+/// result. The next `push` after that starts a new concatenation, clearing
+/// the buffer but keeping its allocation, so a loop performing many
+/// concatenations stops allocating once the buffer has grown to fit the
+/// largest of them.
+///
+/// [push]: #method.push
+/// [finish]: #method.finish
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Default)]
+pub struct ViewConcatenator {
+    buf: Vec<u8>,
+    finished: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl ViewConcatenator {
+    /// Creates a concatenator with an empty buffer, which does not allocate
+    /// until the first view is pushed.
+    pub fn new() -> Self {
+        ViewConcatenator::default()
+    }
+
+    /// Creates a concatenator whose buffer can hold at least `capacity`
+    /// bytes without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ViewConcatenator {
+            buf: Vec::with_capacity(capacity),
+            finished: false,
+        }
+    }
+
+    /// Appends the bytes of `view` to the current concatenation, first
+    /// starting a new one if the previous one was finished.
+    pub fn push(&mut self, view: &CxxStringView) {
+        if self.finished {
+            self.buf.clear();
+            self.finished = false;
+        }
+        self.buf.extend_from_slice(view.as_bytes());
+    }
+
+    /// Ends the current concatenation and returns its bytes.
+    ///
+    /// Calling `finish` again without an intervening `push` returns the same
+    /// bytes; finishing before any push returns an empty slice.
+    pub fn finish(&mut self) -> &[u8] {
+        self.finished = true;
+        &self.buf
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+}
+
+/// A [`CxxStringView`] of the entire contents of a particular [`CxxString`],
+/// which remembers the string it was obtained from.
+///
+/// Produced by [`CxxString::to_string_view_of`]. A plain `CxxStringView` has
+/// no way to tell where its bytes live, but this type keeps the shared
+/// reference to its origin so that the original string can be recovered with
+/// [`origin`][origin]. It dereferences to `CxxStringView` for everything else.
+///
+/// [origin]: #method.origin
+pub struct CxxStringViewOf<'a> {
+    view: CxxStringView<'a>,
+    origin: &'a CxxString,
+}
+
+impl<'a> CxxStringViewOf<'a> {
+    pub(crate) fn new(origin: &'a CxxString) -> Self {
+        CxxStringViewOf {
+            view: CxxStringView::new(origin.as_bytes()),
+            origin,
+        }
+    }
+
+    /// Returns the C++ string that this view was obtained from.
+    pub fn origin(&self) -> &'a CxxString {
+        self.origin
+    }
+
+    /// Discards the origin, returning the underlying string view.
+    pub fn into_view(self) -> CxxStringView<'a> {
+        self.view
+    }
+}
+
+impl<'a> Deref for CxxStringViewOf<'a> {
+    type Target = CxxStringView<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<'a> Display for CxxStringViewOf<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.view, f)
+    }
+}
+
+impl<'a> Debug for CxxStringViewOf<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.view, f)
+    }
+}
+
+/// The pointer and length of a [`CxxStringView`], captured once so that they
+/// can be accessed repeatedly without calling into C++.
+///
+/// Produced by [`CxxStringView::snapshot`]. Every accessor on a
+/// `CxxStringView` goes through the C++ `std::string_view` API, which adds up
+/// in tight loops; the methods here work entirely on the captured slice.
+#[derive(Copy, Clone)]
+pub struct ViewSnapshot<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ViewSnapshot<'a> {
+    /// Returns the length of the captured view in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns true if the captured view has a length of zero bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns a pointer to the first byte of the captured view.
+    ///
+    /// Unlike [`CxxStringView::as_ptr`], this is never null, even for an
+    /// empty view.
+    pub fn as_ptr(&self) -> *const u8 {
+        self.bytes.as_ptr()
+    }
+
+    /// Returns the captured bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Validates that the captured bytes are UTF-8 and returns them as a
+    /// `&str`, otherwise an error.
+    pub fn to_str(&self) -> Result<&'a str, Utf8Error> {
+        str::from_utf8(self.bytes)
+    }
+
+    /// Returns the captured bytes as a `&str`, replacing any invalid UTF-8
+    /// sequences with the U+FFFD replacement character as in
+    /// [`CxxStringView::to_string_lossy`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.bytes)
+    }
+
+    /// Constructs a new string view of the captured bytes.
+    pub fn to_view(&self) -> CxxStringView<'a> {
+        CxxStringView::new(self.bytes)
+    }
+}
+
+impl<'a> Display for ViewSnapshot<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lossy::display(self.bytes, f)
+    }
+}
+
+impl<'a> Debug for ViewSnapshot<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lossy::debug(self.bytes, f)
+    }
+}
+
+/// `Display` adapter for a [`CxxStringView`] which refuses to format invalid
+/// UTF-8.
+///
+/// Produced by [`CxxStringView::strict`]. If the view is valid UTF-8 it is
+/// formatted like `str`, honoring width and precision flags. Otherwise a
+/// description of the [`Utf8Error`], including the byte offset of the invalid
+/// sequence, is written to the formatter and `fmt` returns `Err(fmt::Error)`,
+/// so that logging which should not silently hide encoding problems can
+/// detect them.
+///
+/// # Panics
+///
+/// Because that error does not come from the underlying writer, formatting a
+/// `Strict` of invalid UTF-8 with `ToString::to_string`, `format!`, or
+/// anything else that assumes formatting into a `String` cannot fail will
+/// panic. Format it with `write!` into a `fmt::Write` or `io::Write` and
+/// handle the returned error instead.
+///
+/// ```
+/// # use cxx::CxxStringView;
+/// use std::fmt::Write;
+///
+/// let mut out = String::new();
+/// let result = write!(out, "{}", CxxStringView::new(b"ab\xffcd").strict());
+/// assert!(result.is_err());
+/// assert_eq!(out, "<invalid utf-8 sequence of 1 bytes from index 2>");
+/// ```
+#[derive(Copy, Clone)]
+pub struct Strict<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Display for Strict<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match str::from_utf8(self.bytes) {
+            Ok(s) => Display::fmt(s, f),
+            Err(err) => {
+                write!(f, "<{}>", err)?;
+                Err(fmt::Error)
+            }
+        }
+    }
+}
+
+/// An owned buffer of bytes which can be viewed as a [`CxxStringView`].
+///
+/// `CxxStringViewBuf` is to `CxxStringView` roughly what `String` is to
+/// `&str`: it holds its contents on the Rust heap, so it can be stored and
+/// moved around independently of wherever the bytes originally came from, and
+/// [`to_string_view`][view] lends out a view borrowing from it.
+///
+/// [view]: #method.to_string_view
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CxxStringViewBuf {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl CxxStringViewBuf {
+    /// Constructs an empty buffer.
+    pub fn new() -> Self {
+        CxxStringViewBuf { bytes: Vec::new() }
+    }
+
+    /// Returns the length of the buffer in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns true if the buffer has a length of zero bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns a byte slice of this buffer's contents.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Constructs a string view of the entire contents of this buffer.
+    pub fn to_string_view(&self) -> CxxStringView {
+        CxxStringView::new(&self.bytes)
+    }
+
+    /// Consumes the buffer, returning its contents.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Vec<u8>> for CxxStringViewBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        CxxStringViewBuf { bytes }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<String> for CxxStringViewBuf {
+    fn from(string: String) -> Self {
+        CxxStringViewBuf {
+            bytes: string.into_bytes(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<CxxStringView<'a>> for CxxStringViewBuf {
+    fn from(view: CxxStringView<'a>) -> Self {
+        view.to_owned_buf()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for CxxStringViewBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lossy::display(&self.bytes, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Debug for CxxStringViewBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lossy::debug(&self.bytes, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<[u8]> for CxxStringViewBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Borrow<[u8]> for CxxStringViewBuf {
+    fn borrow(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+// Implements PartialEq and PartialOrd in both directions between two
+// view-like types, by comparing their byte contents.
+macro_rules! impl_bytewise_cmp {
+    ($(#[$attr:meta])* impl<$($lt:lifetime)?> $lhs:ty, $rhs:ty) => {
+        $(#[$attr])*
+        impl<$($lt)?> PartialEq<$rhs> for $lhs {
+            fn eq(&self, other: &$rhs) -> bool {
+                self.as_bytes() == other.as_bytes()
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($lt)?> PartialEq<$lhs> for $rhs {
+            fn eq(&self, other: &$lhs) -> bool {
+                self.as_bytes() == other.as_bytes()
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($lt)?> PartialOrd<$rhs> for $lhs {
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                self.as_bytes().partial_cmp(other.as_bytes())
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($lt)?> PartialOrd<$lhs> for $rhs {
+            fn partial_cmp(&self, other: &$lhs) -> Option<Ordering> {
+                self.as_bytes().partial_cmp(other.as_bytes())
+            }
+        }
+    };
+}
+
+impl_bytewise_cmp!(#[cfg(feature = "alloc")] impl<'a> CxxStringViewBuf, CxxStringView<'a>);
+impl_bytewise_cmp!(#[cfg(feature = "alloc")] impl<> CxxStringViewBuf, str);
+impl_bytewise_cmp!(#[cfg(feature = "alloc")] impl<> CxxStringViewBuf, CxxString);
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::checked_total_len;
+    use core::iter;
+
+    #[test]
+    fn test_checked_total_len() {
+        assert_eq!(checked_total_len(iter::empty()), Some(0));
+        assert_eq!(checked_total_len([6, 6, 0, 5].iter().copied()), Some(17));
+        assert_eq!(
+            checked_total_len([usize::MAX, 0].iter().copied()),
+            Some(usize::MAX)
+        );
+        assert_eq!(checked_total_len([usize::MAX, 1].iter().copied()), None);
+        let half = usize::MAX / 2 + 1;
+        assert_eq!(checked_total_len([half, half, 0].iter().copied()), None);
     }
 }
@@ -1,5 +1,7 @@
 #![cfg(feature = "alloc")]
 
+#[cfg(any(feature = "c++17", feature = "c++20"))]
+use crate::CxxStringView;
 use alloc::boxed::Box;
 use core::fmt::{self, Display};
 
@@ -25,4 +27,15 @@ impl Exception {
     pub fn what(&self) -> &str {
         &self.what
     }
+
+    /// Returns the exception message as a string view, without copying it.
+    ///
+    /// The view borrows the same message as [`what`][what], so it is as cheap
+    /// to obtain and lives as long as `self`.
+    ///
+    /// [what]: #method.what
+    #[cfg(any(feature = "c++17", feature = "c++20"))]
+    pub fn what_view(&self) -> CxxStringView {
+        CxxStringView::new(&*self.what)
+    }
 }
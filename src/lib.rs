@@ -18,7 +18,7 @@
 //!
 //! <br>
 //!
-//! *Compiler support: requires rustc 1.48+ and c++11 or newer*<br>
+//! *Compiler support: requires rustc 1.51+ and c++11 or newer*<br>
 //! *[Release notes](https://github.com/dtolnay/cxx/releases)*
 //!
 //! <br>
@@ -471,9 +471,16 @@ pub use crate::cxx_vector::CxxVector;
 pub use crate::exception::Exception;
 pub use crate::extern_type::{kind, ExternType};
 pub use crate::shared_ptr::SharedPtr;
-pub use crate::string::CxxString;
+#[cfg(feature = "alloc")]
+pub use crate::string::into_cxx_string;
+pub use crate::string::{CxxString, CxxStringWriter};
 #[cfg(any(feature = "c++17", feature = "c++20"))]
-pub use crate::string_view::CxxStringView;
+pub use crate::string_view::{
+    join_into_cxx_string, CxxStringView, CxxStringViewOf, SplitError, Strict, ViewError,
+    ViewPattern, ViewSnapshot,
+};
+#[cfg(all(any(feature = "c++17", feature = "c++20"), feature = "alloc"))]
+pub use crate::string_view::{try_concat_views, CxxStringViewBuf, ViewConcatenator};
 pub use crate::unique_ptr::UniquePtr;
 pub use crate::weak_ptr::WeakPtr;
 pub use cxxbridge_macro::bridge;
@@ -486,6 +493,10 @@ pub use cxxbridge_macro::bridge;
 pub type String = CxxString;
 
 /// Synonym for `CxxStringView`.
+///
+/// The name `StringView` is also accepted in place of `CxxStringView` within
+/// a `#[cxx::bridge]` module, unless that module declares a type of its own
+/// named `StringView`.
 #[cfg(any(feature = "c++17", feature = "c++20"))]
 pub type StringView<'a> = CxxStringView<'a>;
 
@@ -52,9 +52,75 @@ pub fn parse_items(
             Item::Other(item) => cx.error(item, "unsupported item"),
         }
     }
+    resolve_string_view_synonym(&mut apis);
     apis
 }
 
+// Unless the bridge declares a type of its own by that name, `StringView` is
+// a synonym for CxxStringView, like the cxx::StringView type alias.
+fn resolve_string_view_synonym(apis: &mut [Api]) {
+    let declared = apis.iter().any(|api| match api {
+        Api::Struct(strct) => strct.name.rust == "StringView",
+        Api::Enum(enm) => enm.name.rust == "StringView",
+        Api::CxxType(ety) | Api::RustType(ety) => ety.name.rust == "StringView",
+        Api::TypeAlias(alias) => alias.name.rust == "StringView",
+        Api::Include(_) | Api::CxxFunction(_) | Api::RustFunction(_) | Api::Impl(_) => false,
+    });
+    if declared {
+        return;
+    }
+
+    for api in apis {
+        match api {
+            Api::Struct(strct) => {
+                for field in &mut strct.fields {
+                    resolve_string_view_in_type(&mut field.ty);
+                }
+            }
+            Api::CxxFunction(efn) | Api::RustFunction(efn) => {
+                resolve_string_view_in_signature(&mut efn.sig);
+            }
+            Api::Impl(imp) => resolve_string_view_in_type(&mut imp.ty),
+            Api::Include(_)
+            | Api::Enum(_)
+            | Api::CxxType(_)
+            | Api::RustType(_)
+            | Api::TypeAlias(_) => {}
+        }
+    }
+}
+
+fn resolve_string_view_in_signature(sig: &mut Signature) {
+    for arg in &mut sig.args {
+        resolve_string_view_in_type(&mut arg.ty);
+    }
+    if let Some(ret) = &mut sig.ret {
+        resolve_string_view_in_type(ret);
+    }
+}
+
+fn resolve_string_view_in_type(ty: &mut Type) {
+    match ty {
+        Type::Ident(ident) => {
+            if ident.rust == "StringView" {
+                ident.rust = Ident::new("CxxStringView", ident.rust.span());
+            }
+        }
+        Type::Str(_) | Type::Void(_) => {}
+        Type::RustBox(ty)
+        | Type::UniquePtr(ty)
+        | Type::SharedPtr(ty)
+        | Type::WeakPtr(ty)
+        | Type::CxxVector(ty)
+        | Type::RustVec(ty) => resolve_string_view_in_type(&mut ty.inner),
+        Type::Ref(r) => resolve_string_view_in_type(&mut r.inner),
+        Type::Ptr(p) => resolve_string_view_in_type(&mut p.inner),
+        Type::Array(a) => resolve_string_view_in_type(&mut a.inner),
+        Type::SliceRef(s) => resolve_string_view_in_type(&mut s.inner),
+        Type::Fn(sig) => resolve_string_view_in_signature(sig),
+    }
+}
+
 fn parse_struct(cx: &mut Errors, mut item: ItemStruct, namespace: &Namespace) -> Result<Api> {
     let mut cfg = CfgExpr::Unconditional;
     let mut doc = Doc::new();
@@ -532,6 +598,7 @@ fn parse_extern_fn(
     let mut namespace = namespace.clone();
     let mut cxx_name = None;
     let mut rust_name = None;
+    let mut string_view = None;
     let mut attrs = attrs.clone();
     attrs.extend(attrs::parse(
         cx,
@@ -542,6 +609,7 @@ fn parse_extern_fn(
             namespace: Some(&mut namespace),
             cxx_name: Some(&mut cxx_name),
             rust_name: Some(&mut rust_name),
+            string_view: Some(&mut string_view),
             ..Default::default()
         },
     ));
@@ -666,7 +734,10 @@ fn parse_extern_fn(
     }
 
     let mut throws_tokens = None;
-    let ret = parse_return_type(&foreign_fn.sig.output, &mut throws_tokens)?;
+    let mut ret = parse_return_type(&foreign_fn.sig.output, &mut throws_tokens)?;
+    if let Some(attr) = string_view {
+        ret = Some(parse_string_view_return_type(attr, lang, ret)?);
+    }
     let throws = throws_tokens.is_some();
     let asyncness = foreign_fn.sig.asyncness;
     let unsafety = foreign_fn.sig.unsafety;
@@ -1468,6 +1539,36 @@ fn parse_return_type(
     }
 }
 
+// Under #[string_view], a return type written as `&str` or `&'a str` stands
+// for `CxxStringView` or `CxxStringView<'a>` respectively.
+fn parse_string_view_return_type(attr: Attribute, lang: Lang, ret: Option<Type>) -> Result<Type> {
+    if let Lang::Rust = lang {
+        return Err(Error::new_spanned(
+            attr,
+            "#[string_view] is only supported on extern \"C++\" functions",
+        ));
+    }
+
+    let reference = match ret {
+        Some(Type::Str(reference)) if !reference.mutable => reference,
+        _ => {
+            return Err(Error::new_spanned(
+                attr,
+                "#[string_view] requires a return type of &str",
+            ));
+        }
+    };
+
+    let span = reference.ampersand.span;
+    let mut ty = NamedType::new(Ident::new("CxxStringView", span));
+    if let Some(lifetime) = reference.lifetime {
+        ty.generics.lt_token = Some(Token![<](span));
+        ty.generics.lifetimes.push_value(lifetime);
+        ty.generics.gt_token = Some(Token![>](span));
+    }
+    Ok(Type::Ident(ty))
+}
+
 fn visibility_pub(vis: &Visibility, inherited: Span) -> Token![pub] {
     Token![pub](match vis {
         Visibility::Public(vis) => vis.pub_token.span,
@@ -32,3 +32,23 @@ fn test_impl_annotation() {
     let output = str::from_utf8(&generated.implementation).unwrap();
     assert!(output.contains("ANNOTATION void cxxbridge1$do_cpp_thing(::rust::Str foo)"));
 }
+
+const BRIDGE1: &str = r#"
+    #[cxx::bridge]
+    mod ffi {
+        unsafe extern "C++" {
+            type StringView;
+            pub fn do_cpp_thing(foo: &StringView);
+        }
+    }
+"#;
+
+#[test]
+fn test_declared_string_view() {
+    let opt = Opt::default();
+    let source = BRIDGE1.parse().unwrap();
+    let generated = generate_header_and_cc(source, &opt).unwrap();
+    let output = str::from_utf8(&generated.implementation).unwrap();
+    assert!(output.contains("void cxxbridge1$do_cpp_thing(const ::StringView &foo)"));
+    assert!(!output.contains("::std::string_view"));
+}
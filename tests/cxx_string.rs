@@ -13,3 +13,48 @@ fn test_async_cxx_string() {
     fn assert_send(_: impl Send) {}
     assert_send(f());
 }
+
+#[test]
+fn test_reserve_shrink_to_fit() {
+    let_cxx_string!(s = "abc");
+    s.as_mut().reserve(100);
+    assert!(s.capacity() >= 103);
+    assert_eq!(s.as_bytes(), b"abc");
+
+    let ptr = s.as_ptr();
+    s.as_mut().push_str(&"x".repeat(100));
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(s.len(), 103);
+
+    s.as_mut().shrink_to_fit();
+    assert!(s.capacity() >= s.len());
+    assert_eq!(&s.as_bytes()[..4], b"abcx");
+    assert_eq!(s.len(), 103);
+}
+
+#[cfg(any(feature = "c++17", feature = "c++20"))]
+#[test]
+fn test_string_view_of_origin() {
+    let_cxx_string!(s = "origin");
+    let view = s.to_string_view_of();
+    assert_eq!(view.len(), 6);
+    assert_eq!(&view.first_line(), "origin");
+
+    let origin: &CxxString = view.origin();
+    assert_eq!(origin.as_ptr(), s.as_ptr());
+    assert_eq!(origin.to_str().unwrap(), "origin");
+
+    let plain: cxx::CxxStringView = view.into_view();
+    assert_eq!(&plain, "origin");
+}
+
+#[test]
+fn test_into_cxx_string() {
+    let bytes = b"built in \xffRust\0".to_vec();
+    let s = cxx::into_cxx_string(bytes.clone());
+    assert_eq!(s.as_bytes(), &bytes[..]);
+    assert_eq!(s.len(), bytes.len());
+
+    let empty = cxx::into_cxx_string(Vec::new());
+    assert!(empty.is_empty());
+}
@@ -10,3 +10,1429 @@ fn test_cxx_string_view() {
 
     assert_eq!(&sv, "A string from C++");
 }
+
+#[test]
+fn test_split_whitespace() {
+    let sv = cxx::CxxStringView::new("  a bc\t\n d  \r\n");
+    let tokens: Vec<_> = sv.split_whitespace().collect();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(&tokens[0], "a");
+    assert_eq!(&tokens[1], "bc");
+    assert_eq!(&tokens[2], "d");
+
+    assert_eq!(cxx::CxxStringView::new(" \t ").split_whitespace().count(), 0);
+    assert_eq!(cxx::CxxStringView::empty().split_whitespace().count(), 0);
+}
+
+#[test]
+fn test_to_string_lossy_into() {
+    let mut buf = String::from(">");
+    cxx::CxxStringView::new("abc").to_string_lossy_into(&mut buf);
+    cxx::CxxStringView::empty().to_string_lossy_into(&mut buf);
+    cxx::CxxStringView::new(b"d\xffe").to_string_lossy_into(&mut buf);
+    assert_eq!(buf, ">abcd\u{fffd}e");
+}
+
+#[test]
+fn test_bytes_rev() {
+    let sv = cxx::CxxStringView::new("abc");
+    assert_eq!(sv.bytes_rev().collect::<Vec<u8>>(), b"cba");
+    assert_eq!(cxx::CxxStringView::empty().bytes_rev().count(), 0);
+}
+
+#[test]
+fn test_first_last_line() {
+    let single = cxx::CxxStringView::new("only");
+    assert_eq!(&single.first_line(), "only");
+    assert_eq!(&single.last_line(), "only");
+
+    let multi = cxx::CxxStringView::new("first\r\nmiddle\nlast");
+    assert_eq!(&multi.first_line(), "first");
+    assert_eq!(&multi.last_line(), "last");
+
+    let trailing = cxx::CxxStringView::new("first\nlast\r\n");
+    assert_eq!(&trailing.first_line(), "first");
+    assert_eq!(&trailing.last_line(), "last");
+
+    assert_eq!(&cxx::CxxStringView::new("\n").last_line(), "");
+    assert!(cxx::CxxStringView::empty().first_line().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_to_os_str_lossy_unix() {
+    use std::borrow::Cow;
+    use std::os::unix::ffi::OsStrExt;
+
+    let bytes = b"file\xff.txt";
+    let os = cxx::CxxStringView::new(bytes).to_os_str_lossy();
+    assert!(matches!(os, Cow::Borrowed(_)));
+    assert_eq!(os.as_bytes(), bytes);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_to_path_buf() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let source = b"/tmp/report\xff.txt".to_vec();
+    let path = cxx::CxxStringView::new(&source).to_path_buf();
+    drop(source);
+    assert_eq!(path.as_os_str().as_bytes(), b"/tmp/report\xff.txt");
+    assert_eq!(path.parent(), Some(std::path::Path::new("/tmp")));
+    assert_eq!(path.extension().unwrap().as_bytes(), b"txt");
+}
+
+#[cfg(windows)]
+#[test]
+fn test_to_os_str_lossy_windows() {
+    use std::borrow::Cow;
+
+    let valid = cxx::CxxStringView::new("file.txt").to_os_str_lossy();
+    assert!(matches!(valid, Cow::Borrowed(_)));
+    assert_eq!(valid, "file.txt");
+
+    let invalid = cxx::CxxStringView::new(b"file\xff.txt").to_os_str_lossy();
+    assert!(matches!(invalid, Cow::Owned(_)));
+    assert_eq!(invalid, "file\u{fffd}.txt");
+}
+
+#[test]
+fn test_advance() {
+    let sv = cxx::CxxStringView::new("abcdef");
+    assert_eq!(&sv.advance(0).unwrap(), "abcdef");
+    assert_eq!(&sv.advance(2).unwrap(), "cdef");
+    assert!(sv.advance(6).unwrap().is_empty());
+    assert!(sv.advance(7).is_none());
+}
+
+#[test]
+fn test_eq_cstr() {
+    use std::ffi::CStr;
+
+    let cstr = CStr::from_bytes_with_nul(b"hello\0").unwrap();
+    let sv = cxx::CxxStringView::new("hello");
+    assert!(sv == *cstr);
+    assert!(*cstr == sv);
+
+    let other = cxx::CxxStringView::new("hello\0");
+    assert!(other != *cstr);
+    assert!(*cstr != cxx::CxxStringView::new("help"));
+}
+
+#[test]
+fn test_as_display() {
+    for input in [&b"plain text"[..], b"", b"caf\xc3\xa9", b"bad\xffbyte\xf0\x9f", b"\x80"] {
+        let sv = cxx::CxxStringView::new(input);
+        assert_eq!(sv.as_display().to_string(), sv.to_string());
+        assert_eq!(format!("[{}]", sv.as_display()), format!("[{}]", sv));
+        assert_eq!(format!("{:>10}", sv.as_display()), format!("{:>10}", sv));
+        assert_eq!(format!("{:<8}", sv.as_display()), format!("{:<8}", sv));
+        assert_eq!(format!("{:*^9}", sv.as_display()), format!("{:*^9}", sv));
+        assert_eq!(format!("{:.3}", sv.as_display()), format!("{:.3}", sv));
+    }
+    let sv = cxx::CxxStringView::new(b"ab\xffcd");
+    assert_eq!(format!("{:>6.4}", sv.as_display()), "  ab\u{fffd}c");
+
+    let display = {
+        let sv = cxx::CxxStringView::new("outlives the view");
+        sv.as_display()
+    };
+    assert_eq!(display.to_string(), "outlives the view");
+}
+
+#[test]
+fn test_fmt_strict() {
+    use std::fmt::{self, Display, Write};
+
+    struct Strict<'a>(cxx::CxxStringView<'a>);
+
+    impl<'a> Display for Strict<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt_strict(f)
+        }
+    }
+
+    let mut out = String::new();
+    write!(out, "[{:>6}]", Strict(cxx::CxxStringView::new("ok"))).unwrap();
+    assert_eq!(out, "[    ok]");
+
+    let mut out = String::new();
+    let result = write!(out, "{}", Strict(cxx::CxxStringView::new(b"ab\xffcd")));
+    assert!(result.is_err());
+    assert_eq!(out, "<invalid utf-8 sequence of 1 bytes from index 2>");
+
+    let mut out = String::new();
+    write!(out, "[{:<4.1}]", cxx::CxxStringView::new("ok").strict()).unwrap();
+    assert_eq!(out, "[o   ]");
+
+    let mut out = String::new();
+    let result = write!(out, "{}", cxx::CxxStringView::new(b"\xe2\x82").strict());
+    assert!(result.is_err());
+    assert_eq!(out, "<incomplete utf-8 byte sequence from index 0>");
+}
+
+#[test]
+#[should_panic]
+fn test_strict_to_string_panics() {
+    let _ = cxx::CxxStringView::new(b"ab\xffcd").strict().to_string();
+}
+
+#[test]
+fn test_from_cstr() {
+    use std::ffi::{CStr, CString};
+
+    let cstring = CString::new("hello").unwrap();
+    let sv = cxx::CxxStringView::from_cstr(&cstring);
+    assert_eq!(sv.len(), 5);
+    assert_eq!(&sv, "hello");
+    assert_eq!(sv.as_ptr(), cstring.as_ptr().cast::<u8>());
+
+    let empty = CStr::from_bytes_with_nul(b"\0").unwrap();
+    assert!(cxx::CxxStringView::from_cstr(empty).is_empty());
+}
+
+#[test]
+fn test_chars_count() {
+    assert_eq!(cxx::CxxStringView::new("hello").chars_count(), 5);
+    assert_eq!(cxx::CxxStringView::new("héllo wörld").chars_count(), 11);
+    assert_eq!(cxx::CxxStringView::new("🦀").chars_count(), 1);
+    assert_eq!(cxx::CxxStringView::new(b"a\xffb\xf0\x9f").chars_count(), 4);
+    assert_eq!(cxx::CxxStringView::empty().chars_count(), 0);
+}
+
+#[test]
+fn test_distinct_bytes() {
+    let present = cxx::CxxStringView::new(b"abca\xff").distinct_bytes();
+    let distinct: Vec<u8> = (0..=255u8).filter(|&b| present[b as usize]).collect();
+    assert_eq!(distinct, b"abc\xff");
+
+    let present = cxx::CxxStringView::empty().distinct_bytes();
+    assert!(present.iter().all(|&b| !b));
+}
+
+#[test]
+fn test_map_bytes() {
+    let plain = cxx::CxxStringView::new("Hello");
+    let xored = plain.map_bytes(|b| b ^ 0x5a);
+    assert_eq!(xored, b"\x12\x3f\x36\x36\x35");
+    assert_eq!(cxx::CxxStringView::new(&xored).map_bytes(|b| b ^ 0x5a), b"Hello");
+
+    let rot13 = |b: u8| match b {
+        b'a'..=b'z' => (b - b'a' + 13) % 26 + b'a',
+        b'A'..=b'Z' => (b - b'A' + 13) % 26 + b'A',
+        _ => b,
+    };
+    assert_eq!(cxx::CxxStringView::new("Uryyb, jbeyq!").map_bytes(rot13), b"Hello, world!");
+    assert!(cxx::CxxStringView::empty().map_bytes(rot13).is_empty());
+}
+
+#[test]
+fn test_to_ascii_lowercase_cxx_string() {
+    let sv = cxx::CxxStringView::new(b"Hello, WORLD! \xc3\x89t\xc3\xa9 \xff");
+    let lowercase = sv.to_ascii_lowercase_cxx_string();
+    assert_eq!(lowercase.as_bytes(), b"hello, world! \xc3\x89t\xc3\xa9 \xff");
+
+    let long = "ABC".repeat(200);
+    let lowercase = cxx::CxxStringView::new(&long).to_ascii_lowercase_cxx_string();
+    assert_eq!(lowercase.to_str().unwrap(), "abc".repeat(200));
+
+    assert!(cxx::CxxStringView::empty().to_ascii_lowercase_cxx_string().is_empty());
+}
+
+#[test]
+fn test_to_ascii_titlecase() {
+    let sv = cxx::CxxStringView::new("the quick\tbrown  fox");
+    assert_eq!(sv.to_ascii_titlecase(), "The Quick\tBrown  Fox");
+    let sv = cxx::CxxStringView::new("  leading and trailing ");
+    assert_eq!(sv.to_ascii_titlecase(), "  Leading And Trailing ");
+    let sv = cxx::CxxStringView::new(b"\xffbad 1st \xc3\xa9t\xc3\xa9 mIxEd");
+    assert_eq!(sv.to_ascii_titlecase(), "\u{fffd}Bad 1St \u{e9}T\u{e9} MIxEd");
+    let sv = cxx::CxxStringView::new("(hello) 'quoted' -- 42");
+    assert_eq!(sv.to_ascii_titlecase(), "(Hello) 'Quoted' -- 42");
+    assert_eq!(cxx::CxxStringView::empty().to_ascii_titlecase(), "");
+}
+
+#[test]
+fn test_csv_fields() {
+    use std::borrow::Cow;
+
+    let sv = cxx::CxxStringView::new(r#"plain,"quoted, with comma","say ""hi""",,"""""#);
+    let fields: Vec<Cow<[u8]>> = sv.csv_fields().collect();
+    assert_eq!(fields.len(), 5);
+    assert_eq!(fields[0], &b"plain"[..]);
+    assert_eq!(fields[1], &b"quoted, with comma"[..]);
+    assert!(matches!(fields[1], Cow::Borrowed(_)));
+    assert_eq!(fields[2], &b"say \"hi\""[..]);
+    assert!(matches!(fields[2], Cow::Owned(_)));
+    assert_eq!(fields[3], &b""[..]);
+    assert_eq!(fields[4], &b"\""[..]);
+
+    let fields: Vec<_> = cxx::CxxStringView::new(r#""a"b,"unterminated, "#).csv_fields().collect();
+    assert_eq!(fields, [&b"ab"[..], &b"unterminated, "[..]]);
+
+    let fields: Vec<_> = cxx::CxxStringView::new("x,").csv_fields().collect();
+    assert_eq!(fields, [&b"x"[..], &b""[..]]);
+    assert_eq!(cxx::CxxStringView::empty().csv_fields().count(), 1);
+}
+
+#[test]
+fn test_split_non_empty() {
+    let sv = cxx::CxxStringView::new("//usr//local/bin/");
+    let parts: Vec<_> = sv.split_non_empty(b'/').collect();
+    assert_eq!(parts.len(), 3);
+    assert_eq!(&parts[0], "usr");
+    assert_eq!(&parts[1], "local");
+    assert_eq!(&parts[2], "bin");
+
+    assert_eq!(sv.split(b'/').count(), 7);
+    assert_eq!(cxx::CxxStringView::new("///").split_non_empty(b'/').count(), 0);
+    assert_eq!(cxx::CxxStringView::empty().split_non_empty(b'/').count(), 0);
+}
+
+#[test]
+fn test_find_nth() {
+    let sv = cxx::CxxStringView::new("a::b::c::d");
+    assert_eq!(sv.find_nth("::", 0), Some(1));
+    assert_eq!(sv.find_nth("::", 2), Some(7));
+    assert_eq!(sv.find_nth("::", 3), None);
+    assert_eq!(sv.find_nth(&b"d"[..], 0), Some(9));
+    assert_eq!(sv.find_nth("x", 0), None);
+
+    let overlapping = cxx::CxxStringView::new("aaaa");
+    assert_eq!(overlapping.find_nth("aa", 1), Some(2));
+    assert_eq!(overlapping.find_nth("aa", 2), None);
+
+    assert_eq!(cxx::CxxStringView::new("ab").find_nth("", 2), Some(2));
+    assert_eq!(cxx::CxxStringView::new("ab").find_nth("", 3), None);
+    assert_eq!(cxx::CxxStringView::empty().find_nth("a", 0), None);
+}
+
+#[test]
+fn test_matches_any_prefix() {
+    let sv = cxx::CxxStringView::new("GETX /index.html");
+    assert_eq!(sv.matches_any_prefix(&["POST", "GETX", "GET"]), Some(1));
+    assert_eq!(sv.matches_any_prefix(&["POST", "GET", "GETX"]), Some(1));
+    assert_eq!(sv.matches_any_prefix(&["G", "GETX"]), Some(0));
+    assert_eq!(sv.matches_any_prefix(&["PUT", "DELETE"]), None);
+    assert_eq!(sv.matches_any_prefix::<&str>(&[]), None);
+
+    let tags: [&[u8]; 3] = [b"\x89PNG", b"GIF8", b""];
+    assert_eq!(cxx::CxxStringView::new(b"GIF89a").matches_any_prefix(&tags), Some(1));
+    assert_eq!(cxx::CxxStringView::new(b"BM").matches_any_prefix(&tags), Some(2));
+    assert_eq!(cxx::CxxStringView::empty().matches_any_prefix(&[vec![b'x']]), None);
+}
+
+#[test]
+fn test_as_array() {
+    let sv = cxx::CxxStringView::new(b"\x89PNG\r\n");
+    let [a, b, c, d] = *sv.as_array().unwrap();
+    assert_eq!([a, b, c, d], *b"\x89PNG");
+    assert_eq!(sv.as_array::<6>(), Some(b"\x89PNG\r\n"));
+    assert_eq!(sv.as_array::<7>(), None);
+    assert_eq!(sv.as_array::<0>(), Some(&[]));
+    assert_eq!(cxx::CxxStringView::empty().as_array::<1>(), None);
+}
+
+#[test]
+fn test_context() {
+    let sv = cxx::CxxStringView::new("0123456789");
+    assert_eq!(&sv.context(5, 2), "3456");
+    assert_eq!(&sv.context(1, 3), "0123");
+    assert_eq!(&sv.context(0, 2), "01");
+    assert_eq!(&sv.context(9, 2), "789");
+    assert_eq!(&sv.context(10, 2), "89");
+    assert_eq!(&sv.context(100, 2), "89");
+    assert_eq!(&sv.context(5, 0), "");
+    assert_eq!(&sv.context(5, usize::MAX), "0123456789");
+    assert!(cxx::CxxStringView::empty().context(0, 4).is_empty());
+}
+
+#[test]
+fn test_prefix_len_in_set() {
+    let digits = b"0123456789";
+    assert_eq!(cxx::CxxStringView::new("2024").prefix_len_in_set(digits), 4);
+    assert_eq!(cxx::CxxStringView::new("12:30").prefix_len_in_set(digits), 2);
+    assert_eq!(cxx::CxxStringView::new("x12").prefix_len_in_set(digits), 0);
+    assert_eq!(cxx::CxxStringView::new(" \t\tx ").prefix_len_in_set(b" \t"), 3);
+    assert_eq!(cxx::CxxStringView::new("abc").prefix_len_in_set(b""), 0);
+    assert_eq!(cxx::CxxStringView::empty().prefix_len_in_set(digits), 0);
+}
+
+#[test]
+fn test_take_while() {
+    let sv = cxx::CxxStringView::new("123abc");
+    let (digits, rest) = sv.take_while(|b| b.is_ascii_digit());
+    assert_eq!(&digits, "123");
+    assert_eq!(&rest, "abc");
+
+    let (none, rest) = rest.take_while(|b| b.is_ascii_digit());
+    assert!(none.is_empty());
+    assert_eq!(&rest, "abc");
+
+    let (all, rest) = rest.take_while(|b| b.is_ascii_alphabetic());
+    assert_eq!(&all, "abc");
+    assert!(rest.is_empty());
+
+    let (taken, rest) = cxx::CxxStringView::empty().take_while(|_| true);
+    assert!(taken.is_empty() && rest.is_empty());
+}
+
+#[test]
+fn test_byte_lines() {
+    let sv = cxx::CxxStringView::new("one\r\ntwo\n\nthree\r");
+    let lines: Vec<_> = sv.byte_lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(&lines[0], "one\r");
+    assert_eq!(&lines[1], "two");
+    assert_eq!(&lines[2], "");
+    assert_eq!(&lines[3], "three\r");
+
+    let sv = cxx::CxxStringView::new(b"\xff\r\n");
+    let lines: Vec<_> = sv.byte_lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].as_bytes(), b"\xff\r");
+
+    assert_eq!(cxx::CxxStringView::new("\n").byte_lines().count(), 1);
+    assert_eq!(cxx::CxxStringView::empty().byte_lines().count(), 0);
+}
+
+#[test]
+fn test_normalize_newlines() {
+    let crlf = cxx::CxxStringView::new("a\r\nb\r\n");
+    assert_eq!(crlf.normalize_newlines(), "a\nb\n");
+    let cr = cxx::CxxStringView::new("a\rb\r");
+    assert_eq!(cr.normalize_newlines(), "a\nb\n");
+    let mixed = cxx::CxxStringView::new(b"1\r\n2\r3\n4\r\r\n\n\xff\r");
+    assert_eq!(mixed.normalize_newlines(), "1\n2\n3\n4\n\n\n\u{fffd}\n");
+    assert_eq!(cxx::CxxStringView::empty().normalize_newlines(), "");
+}
+
+#[test]
+fn test_line_offsets() {
+    let sv = cxx::CxxStringView::new("first\r\nsecond\n\r\nlast");
+    let lines: Vec<(usize, String)> = sv
+        .line_offsets()
+        .map(|(offset, line)| (offset, line.to_string()))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            (0, "first".to_owned()),
+            (7, "second".to_owned()),
+            (14, "".to_owned()),
+            (16, "last".to_owned()),
+        ],
+    );
+    assert_eq!(&sv.as_bytes()[16..], b"last");
+
+    assert_eq!(cxx::CxxStringView::new("x\n").line_offsets().count(), 1);
+    assert_eq!(cxx::CxxStringView::empty().line_offsets().count(), 0);
+}
+
+#[test]
+fn test_line_count() {
+    assert_eq!(cxx::CxxStringView::empty().line_count(), 0);
+    assert_eq!(cxx::CxxStringView::new("one").line_count(), 1);
+    assert_eq!(cxx::CxxStringView::new("one\n").line_count(), 1);
+    assert_eq!(cxx::CxxStringView::new("one\ntwo").line_count(), 2);
+    assert_eq!(cxx::CxxStringView::new("one\r\ntwo\r\n").line_count(), 2);
+    assert_eq!(cxx::CxxStringView::new("\n").line_count(), 1);
+    assert_eq!(cxx::CxxStringView::new("\n\n\nx").line_count(), 4);
+
+    for input in ["", "a", "a\n", "\n\nb\n", "a\n\nb"] {
+        let sv = cxx::CxxStringView::new(input);
+        assert_eq!(sv.line_count(), sv.byte_lines().count(), "{:?}", input);
+    }
+}
+
+#[test]
+fn test_utf8_segments() {
+    let sv = cxx::CxxStringView::new(b"\x00\x01header\xff\xfe\xc3\xa9t\xc3\xa9\xc3\x80\xe2\x82");
+    let segments: Vec<&str> = sv.utf8_segments().collect();
+    assert_eq!(segments, ["\0\u{1}header", "\u{e9}t\u{e9}\u{c0}"]);
+
+    let segments: Vec<&str> = cxx::CxxStringView::new("all valid").utf8_segments().collect();
+    assert_eq!(segments, ["all valid"]);
+    assert_eq!(cxx::CxxStringView::new(b"\xff\x80").utf8_segments().count(), 0);
+    assert_eq!(cxx::CxxStringView::empty().utf8_segments().count(), 0);
+}
+
+#[test]
+fn test_split_n() {
+    let sv = cxx::CxxStringView::new("GET,/index.html,HTTP/1.1");
+    let [method, path, version] = sv.split_n(b',').unwrap();
+    assert_eq!(&method, "GET");
+    assert_eq!(&path, "/index.html");
+    assert_eq!(&version, "HTTP/1.1");
+
+    assert!(sv.split_n::<2>(b',').is_none());
+    assert!(sv.split_n::<4>(b',').is_none());
+    assert!(sv.split_n::<0>(b',').is_none());
+
+    let [whole] = sv.split_n(b';').unwrap();
+    assert_eq!(whole, sv);
+    let [a, b] = cxx::CxxStringView::new(",").split_n(b',').unwrap();
+    assert!(a.is_empty() && b.is_empty());
+    let [empty] = cxx::CxxStringView::empty().split_n(b',').unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_empty_as_bytes() {
+    let backing = *b"abc";
+    let views = [
+        cxx::CxxStringView::empty(),
+        cxx::CxxStringView::new(""),
+        cxx::CxxStringView::new(&backing[..0]),
+        cxx::CxxStringView::new(&backing[3..]),
+    ];
+    for sv in &views {
+        let bytes = sv.as_bytes();
+        assert_ne!(bytes.as_ptr(), std::ptr::null());
+        assert_eq!(bytes.len(), 0);
+        assert_eq!(bytes.iter().next(), None);
+        assert_eq!(bytes, b"");
+    }
+
+    // A non-null data pointer of an empty view is kept as is.
+    assert_eq!(views[3].as_bytes().as_ptr(), backing[3..].as_ptr());
+}
+
+#[test]
+fn test_as_bytes_unchecked() {
+    let_cxx_string!(s = "from a CxxString");
+    let views = [
+        s.to_string_view(),
+        cxx::CxxStringView::new("from a str"),
+        cxx::CxxStringView::empty(),
+    ];
+    for sv in &views {
+        let unchecked = unsafe { sv.as_bytes_unchecked() };
+        assert_eq!(unchecked, sv.as_bytes());
+        assert_eq!(unchecked.as_ptr(), sv.as_bytes().as_ptr());
+    }
+}
+
+#[test]
+fn test_is_aligned_for() {
+    let words = [0u64; 2];
+    let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr().cast::<u8>(), 16) };
+
+    let aligned = cxx::CxxStringView::new(bytes);
+    assert!(aligned.is_aligned_for::<u8>());
+    assert!(aligned.is_aligned_for::<u32>());
+    assert!(aligned.is_aligned_for::<u64>());
+
+    let misaligned = cxx::CxxStringView::new(&bytes[1..]);
+    assert!(misaligned.is_aligned_for::<u8>());
+    assert!(!misaligned.is_aligned_for::<u16>());
+    assert!(!misaligned.is_aligned_for::<u64>());
+
+    assert!(cxx::CxxStringView::new(&bytes[4..]).is_aligned_for::<u32>());
+    assert!(cxx::CxxStringView::empty().is_aligned_for::<u64>());
+}
+
+#[test]
+fn test_join_into_cxx_string() {
+    let words = ["alpha", "", "gamma", "delta"];
+    let views: Vec<_> = words.iter().map(cxx::CxxStringView::new).collect();
+
+    let_cxx_string!(joined = "");
+    cxx::join_into_cxx_string(joined.as_mut(), &views, cxx::CxxStringView::new(", "));
+    assert_eq!(joined.to_str().unwrap(), words.join(", "));
+    assert!(joined.capacity() >= joined.len());
+
+    let_cxx_string!(appended = "> ");
+    cxx::join_into_cxx_string(appended.as_mut(), &views[..1], cxx::CxxStringView::new("-"));
+    cxx::join_into_cxx_string(appended.as_mut(), &[], cxx::CxxStringView::new("-"));
+    assert_eq!(appended.to_str().unwrap(), "> alpha");
+}
+
+#[test]
+fn test_reinterpret_utf16() {
+    let units: Vec<u16> = "héllo".encode_utf16().collect();
+    let bytes = unsafe { std::slice::from_raw_parts(units.as_ptr().cast::<u8>(), units.len() * 2) };
+
+    let sv = cxx::CxxStringView::new(bytes);
+    let utf16 = sv.reinterpret_utf16().unwrap();
+    assert_eq!(utf16, &units[..]);
+    assert_eq!(String::from_utf16(utf16).unwrap(), "héllo");
+
+    assert_eq!(cxx::CxxStringView::new(&bytes[..3]).reinterpret_utf16(), None);
+    assert_eq!(cxx::CxxStringView::new(&bytes[1..5]).reinterpret_utf16(), None);
+    assert_eq!(cxx::CxxStringView::new(&bytes[2..6]).reinterpret_utf16(), Some(&units[1..3]));
+    assert_eq!(cxx::CxxStringView::empty().reinterpret_utf16(), Some(&[][..]));
+}
+
+#[test]
+fn test_try_concat_views() {
+    let owned = String::from("middle");
+    let views = [
+        cxx::CxxStringView::new("start-"),
+        cxx::CxxStringView::new(&owned),
+        cxx::CxxStringView::empty(),
+        cxx::CxxStringView::new(b"-\xffend"),
+    ];
+    let concat = cxx::try_concat_views(&views).unwrap();
+    assert_eq!(concat, b"start-middle-\xffend");
+
+    assert_eq!(cxx::try_concat_views(&[]), Some(Vec::new()));
+}
+
+#[test]
+fn test_byte_histogram() {
+    let counts = cxx::CxxStringView::new(b"abca\xff\xff\xff").byte_histogram();
+    assert_eq!(counts[b'a' as usize], 2);
+    assert_eq!(counts[b'b' as usize], 1);
+    assert_eq!(counts[b'c' as usize], 1);
+    assert_eq!(counts[0xff], 3);
+    assert_eq!(counts.iter().sum::<usize>(), 7);
+
+    let counts = cxx::CxxStringView::empty().byte_histogram();
+    assert!(counts.iter().all(|&n| n == 0));
+}
+
+#[test]
+fn test_shannon_entropy() {
+    assert_eq!(cxx::CxxStringView::empty().shannon_entropy(), 0.0);
+    assert_eq!(cxx::CxxStringView::new("aaaaaaaa").shannon_entropy(), 0.0);
+    assert!((cxx::CxxStringView::new("abab").shannon_entropy() - 1.0).abs() < 1e-9);
+
+    let uniform: Vec<u8> = (0..4096).map(|i| (i * 167 % 256) as u8).collect();
+    let entropy = cxx::CxxStringView::new(&uniform).shannon_entropy();
+    assert!((entropy - 8.0).abs() < 1e-9, "{}", entropy);
+}
+
+#[test]
+fn test_eq_trimmed() {
+    let a = cxx::CxxStringView::new("token");
+    let b = cxx::CxxStringView::new("  token\t\r\n");
+    assert!(a != b);
+    assert!(a.eq_trimmed(&b));
+    assert!(b.eq_trimmed(&a));
+    assert!(!a.eq_trimmed(&cxx::CxxStringView::new(" to ken ")));
+    assert!(cxx::CxxStringView::new(" \n").eq_trimmed(&cxx::CxxStringView::empty()));
+}
+
+#[test]
+fn test_encode_utf16_lossy() {
+    let sv = cxx::CxxStringView::new("aé🦀");
+    assert_eq!(sv.encode_utf16_lossy(), [0x61, 0xe9, 0xd83e, 0xdd80]);
+
+    let invalid = cxx::CxxStringView::new(b"a\xff");
+    assert_eq!(invalid.encode_utf16_lossy(), [0x61, 0xfffd]);
+}
+
+#[test]
+fn test_split_inclusive() {
+    let sv = cxx::CxxStringView::new("a\nbc\n\nd");
+    let inclusive: Vec<_> = sv.split_inclusive(b'\n').collect();
+    assert_eq!(inclusive.len(), 4);
+    assert_eq!(&inclusive[0], "a\n");
+    assert_eq!(&inclusive[1], "bc\n");
+    assert_eq!(&inclusive[2], "\n");
+    assert_eq!(&inclusive[3], "d");
+
+    // Same segments as the regular split, just with the delimiters retained.
+    let plain: Vec<&[u8]> = sv.as_bytes().split(|&b| b == b'\n').collect();
+    for (inclusive, plain) in inclusive.iter().zip(plain) {
+        let bytes = inclusive.as_bytes();
+        let trimmed = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+        assert_eq!(trimmed, plain);
+    }
+
+    let trailing = cxx::CxxStringView::new("a\nb\n");
+    assert_eq!(trailing.split_inclusive(b'\n').count(), 2);
+    assert_eq!(cxx::CxxStringView::empty().split_inclusive(b'\n').count(), 0);
+}
+
+#[test]
+fn test_binary_search_byte() {
+    let sv = cxx::CxxStringView::new("acegik");
+    assert_eq!(sv.binary_search_byte(b'a'), Ok(0));
+    assert_eq!(sv.binary_search_byte(b'g'), Ok(3));
+    assert_eq!(sv.binary_search_byte(b'd'), Err(2));
+    assert_eq!(sv.binary_search_byte(b'z'), Err(6));
+    assert_eq!(cxx::CxxStringView::empty().binary_search_byte(b'a'), Err(0));
+}
+
+#[test]
+fn test_reader() {
+    use std::io::Read;
+
+    let mut reader = cxx::CxxStringView::new("hello world").reader();
+    let mut head = [0; 5];
+    reader.read_exact(&mut head).unwrap();
+    assert_eq!(&head, b"hello");
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b" world");
+}
+
+#[test]
+fn test_view_pattern() {
+    use cxx::ViewPattern;
+
+    let sv = cxx::CxxStringView::new("key = value; other");
+    assert_eq!(sv.find(b'='), Some(4));
+    assert_eq!(sv.find(&b";="[..]), Some(4));
+    assert_eq!(sv.find(|b: u8| b.is_ascii_punctuation()), Some(4));
+    let semicolon: ViewPattern = ViewPattern::Set(b";");
+    assert_eq!(sv.find(semicolon), Some(11));
+    assert_eq!(sv.find(b'#'), None);
+
+    let fields: Vec<_> = cxx::CxxStringView::new("a,b;;c").split(&b",;"[..]).collect();
+    assert_eq!(fields.len(), 4);
+    assert_eq!(&fields[0], "a");
+    assert_eq!(&fields[1], "b");
+    assert!(fields[2].is_empty());
+    assert_eq!(&fields[3], "c");
+    assert_eq!(cxx::CxxStringView::empty().split(b',').count(), 1);
+
+    let padded = cxx::CxxStringView::new("--==x-y==--");
+    assert_eq!(&padded.trim_matches(b'-'), "==x-y==");
+    assert_eq!(&padded.trim_matches(|b| b == b'-' || b == b'='), "x-y");
+    assert!(padded.trim_matches(|_| true).is_empty());
+}
+
+#[test]
+fn test_char_boundary() {
+    // 'é' is 2 bytes at 1..3 and '🦀' is 4 bytes at 3..7.
+    let sv = cxx::CxxStringView::new("aé🦀b");
+    assert_eq!(sv.floor_char_boundary(0), 0);
+    assert_eq!(sv.floor_char_boundary(2), 1);
+    assert_eq!(sv.floor_char_boundary(5), 3);
+    assert_eq!(sv.floor_char_boundary(7), 7);
+    assert_eq!(sv.floor_char_boundary(100), 8);
+
+    assert_eq!(sv.ceil_char_boundary(0), 0);
+    assert_eq!(sv.ceil_char_boundary(2), 3);
+    assert_eq!(sv.ceil_char_boundary(4), 7);
+    assert_eq!(sv.ceil_char_boundary(8), 8);
+    assert_eq!(sv.ceil_char_boundary(100), 8);
+}
+
+#[test]
+fn test_into_static_copy() {
+    let leaked: cxx::CxxStringView<'static> = {
+        let source = String::from("plugin name");
+        let sv = cxx::CxxStringView::new(&source);
+        sv.into_static_copy()
+    };
+    assert_eq!(&leaked, "plugin name");
+}
+
+#[test]
+fn test_first_difference() {
+    let abc = cxx::CxxStringView::new("abc");
+    assert_eq!(abc.first_difference(&cxx::CxxStringView::new("abc")), None);
+    assert_eq!(abc.first_difference(&cxx::CxxStringView::new("abcdef")), Some(3));
+    assert_eq!(abc.first_difference(&cxx::CxxStringView::new("ab")), Some(2));
+    assert_eq!(abc.first_difference(&cxx::CxxStringView::new("axc")), Some(1));
+    assert_eq!(abc.first_difference(&cxx::CxxStringView::empty()), Some(0));
+}
+
+#[test]
+fn test_as_ascii() {
+    assert_eq!(cxx::CxxStringView::new("GET /").as_ascii(), Some(&b"GET /"[..]));
+    assert_eq!(cxx::CxxStringView::empty().as_ascii(), Some(&b""[..]));
+    assert_eq!(cxx::CxxStringView::new("café").as_ascii(), None);
+    assert_eq!(cxx::CxxStringView::new(b"\x80").as_ascii(), None);
+}
+
+#[test]
+fn test_to_str_if_ascii() {
+    assert_eq!(cxx::CxxStringView::new("plain ascii\n").to_str_if_ascii(), Some("plain ascii\n"));
+    assert_eq!(cxx::CxxStringView::empty().to_str_if_ascii(), Some(""));
+    assert_eq!(cxx::CxxStringView::new("caf\u{e9}").to_str_if_ascii(), None);
+    assert_eq!(cxx::CxxStringView::new(b"\x80").to_str_if_ascii(), None);
+}
+
+#[test]
+fn test_view_buf_cmp() {
+    use cxx::CxxStringViewBuf;
+
+    let owned = CxxStringViewBuf::from(String::from("beta"));
+    let same = cxx::CxxStringView::new("beta");
+    let lesser = cxx::CxxStringView::new("alpha");
+
+    assert!(owned == same);
+    assert!(same == owned);
+    assert!(owned != lesser);
+    assert!(owned > lesser);
+    assert!(lesser < owned);
+    assert!(owned == *"beta");
+    assert!(owned.to_string_view() == same);
+
+    let_cxx_string!(s = "beta");
+    assert!(owned == *s);
+    assert!(*s == owned);
+}
+
+#[rustversion::since(1.80)]
+#[test]
+fn test_trim_ascii() {
+    for input in [&b"  \t hello world \r\n"[..], b"hello", b"", b" \n ", b"\x0bx\x0b"] {
+        let sv = cxx::CxxStringView::new(input);
+        assert_eq!(sv.trim_ascii().as_bytes(), input.trim_ascii());
+        assert_eq!(sv.trim_ascii_start().as_bytes(), input.trim_ascii_start());
+        assert_eq!(sv.trim_ascii_end().as_bytes(), input.trim_ascii_end());
+    }
+}
+
+#[test]
+fn test_trim_end_nul() {
+    let sv = cxx::CxxStringView::new(b"name\0\0\0\0");
+    assert_eq!(&sv.trim_end_nul(), "name");
+    let sv = cxx::CxxStringView::new(b"a\0b\0");
+    assert_eq!(sv.trim_end_nul().as_bytes(), b"a\0b");
+    let sv = cxx::CxxStringView::new("no nul");
+    assert_eq!(&sv.trim_end_nul(), "no nul");
+    assert!(cxx::CxxStringView::new(b"\0\0").trim_end_nul().is_empty());
+    assert!(cxx::CxxStringView::empty().trim_end_nul().is_empty());
+}
+
+#[cfg(feature = "unicode-width")]
+#[test]
+fn test_display_width() {
+    assert_eq!(cxx::CxxStringView::new("abc").display_width(), 3);
+    assert_eq!(cxx::CxxStringView::new("ａ").display_width(), 2);
+    assert_eq!(cxx::CxxStringView::new("日本語").display_width(), 6);
+    assert_eq!(cxx::CxxStringView::new(b"a\xff").display_width(), 2);
+    assert_eq!(cxx::CxxStringView::empty().display_width(), 0);
+}
+
+#[cfg(feature = "unicode-segmentation")]
+#[test]
+fn test_graphemes() {
+    let sv = cxx::CxxStringView::new("e\u{301}a\u{1f1fa}\u{1f1f8}");
+    assert_eq!(sv.chars_count(), 5);
+    assert_eq!(sv.grapheme_count(), 3);
+    let graphemes: Vec<_> = sv.graphemes().collect();
+    assert_eq!(graphemes, ["e\u{301}", "a", "\u{1f1fa}\u{1f1f8}"]);
+
+    let graphemes: Vec<_> = cxx::CxxStringView::new(b"a\xffb\r\n").graphemes().collect();
+    assert_eq!(graphemes, ["a", "\u{fffd}", "b", "\r\n"]);
+    assert_eq!(cxx::CxxStringView::empty().grapheme_count(), 0);
+}
+
+#[cfg(feature = "unicode-case")]
+#[test]
+fn test_eq_unicode_case_fold() {
+    let sv = cxx::CxxStringView::new("ÉCOLE Ünïcode");
+    assert!(sv.eq_unicode_case_fold(&cxx::CxxStringView::new("école ünÏCODE")));
+    assert!(!sv.eq_unicode_case_fold(&cxx::CxxStringView::new("ecole unicode")));
+    assert!(cxx::CxxStringView::new("ΣΊΣΥΦΟΣ").eq_unicode_case_fold(&cxx::CxxStringView::new("σίσυφοσ")));
+    assert!(cxx::CxxStringView::new("ΣΊΣΥΦΟΣ").eq_unicode_case_fold(&cxx::CxxStringView::new("σίσυφος")));
+    assert!(cxx::CxxStringView::new("ſ µ").eq_unicode_case_fold(&cxx::CxxStringView::new("S Μ")));
+    assert!(!cxx::CxxStringView::new("ß").eq_unicode_case_fold(&cxx::CxxStringView::new("SS")));
+    assert!(!cxx::CxxStringView::new("İ").eq_unicode_case_fold(&cxx::CxxStringView::new("i")));
+    assert!(!cxx::CxxStringView::new("abc").eq_unicode_case_fold(&cxx::CxxStringView::new("abcd")));
+    assert!(cxx::CxxStringView::new(b"\xffA").eq_unicode_case_fold(&cxx::CxxStringView::new(b"\xfea")));
+    assert!(cxx::CxxStringView::empty().eq_unicode_case_fold(&cxx::CxxStringView::empty()));
+}
+
+#[test]
+fn test_split_at_checked() {
+    let sv = cxx::CxxStringView::new("header:body");
+    let (left, right) = sv.split_at_checked(7).unwrap();
+    assert_eq!(&left, "header:");
+    assert_eq!(&right, "body");
+
+    let (left, right) = sv.split_at_checked(11).unwrap();
+    assert_eq!(&left, "header:body");
+    assert!(right.is_empty());
+
+    let err = sv.split_at_checked(12).unwrap_err();
+    assert_eq!(err.mid(), 12);
+    assert_eq!(err.len(), 11);
+    assert_eq!(
+        err.to_string(),
+        "split index 12 is out of range for string view of length 11",
+    );
+}
+
+#[test]
+fn test_into_string() {
+    let sv = cxx::CxxStringView::new("owned");
+    let by_ref = String::from(&sv);
+    let by_value: String = sv.into();
+    assert_eq!(by_ref, "owned");
+    assert_eq!(by_value, "owned");
+
+    let lossy = String::from(cxx::CxxStringView::new(b"a\xffb"));
+    assert_eq!(lossy, "a\u{fffd}b");
+}
+
+#[test]
+fn test_to_hex() {
+    assert_eq!(cxx::CxxStringView::new(b"\x00\x7f\xab\xff").to_hex(), "007fabff");
+    assert_eq!(cxx::CxxStringView::new("Hi").to_hex(), "4869");
+    assert_eq!(cxx::CxxStringView::empty().to_hex(), "");
+
+    let dump = cxx::CxxStringView::new("hello, world!\n\x01 more").to_hex_dump();
+    assert_eq!(
+        dump,
+        "00000000  68 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 01 20  |hello, world!.. |\n\
+         00000010  6d 6f 72 65                                       |more|\n\
+         00000014\n",
+    );
+    assert_eq!(cxx::CxxStringView::empty().to_hex_dump(), "");
+}
+
+#[test]
+fn test_find_long_haystack() {
+    let mut text = "ab".repeat(1000);
+    text.push_str("=x\n;y");
+    let sv = cxx::CxxStringView::new(&text);
+    assert_eq!(sv.find(b'='), Some(2000));
+    assert_eq!(sv.find(&b";="[..]), Some(2000));
+    assert_eq!(sv.find(&b";\n="[..]), Some(2000));
+    assert_eq!(sv.find(&b";\nyx"[..]), Some(2001));
+    assert_eq!(sv.find(b'#'), None);
+    assert_eq!(sv.split(b'=').count(), 2);
+    assert_eq!(sv.line_count(), 2);
+    assert_eq!(&sv.byte_lines().last().unwrap(), ";y");
+    assert_eq!(sv.find_nth("ab", 999), Some(1998));
+    assert_eq!(sv.find_nth("ab", 1000), None);
+}
+
+#[test]
+fn test_to_display_string() {
+    let text = cxx::CxxStringView::new("héllo");
+    assert_eq!(text.to_display_string(), "héllo");
+    assert_eq!(cxx::CxxStringView::empty().to_display_string(), "");
+
+    let binary = cxx::CxxStringView::new(b"a\xffb\x00");
+    assert_eq!(binary.to_display_string(), "<4 bytes: 61 ff 62 00>");
+}
+
+#[test]
+fn test_split_valid_utf8() {
+    let (valid, rest) = cxx::CxxStringView::new(b"caf\xc3\xa9\xffok").split_valid_utf8();
+    assert_eq!(valid, "café");
+    assert_eq!(rest.as_bytes(), b"\xffok");
+
+    let (valid, rest) = cxx::CxxStringView::new(b"ab\xe2\x82").split_valid_utf8();
+    assert_eq!(valid, "ab");
+    assert_eq!(rest.as_bytes(), b"\xe2\x82");
+
+    let (valid, rest) = cxx::CxxStringView::new("all valid").split_valid_utf8();
+    assert_eq!(valid, "all valid");
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn test_try_from_raw_parts() {
+    let bytes = b"raw parts";
+    let sv = unsafe { cxx::CxxStringView::try_from_raw_parts(bytes.as_ptr(), 3) }.unwrap();
+    assert_eq!(&sv, "raw");
+
+    let too_long = isize::MAX as usize + 1;
+    let err = unsafe { cxx::CxxStringView::try_from_raw_parts(bytes.as_ptr(), too_long) }
+        .err()
+        .unwrap();
+    assert_eq!(err.len(), too_long);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "string view length {} exceeds the maximum of isize::MAX bytes",
+            too_long,
+        ),
+    );
+}
+
+#[test]
+fn test_snapshot() {
+    let sv = cxx::CxxStringView::new(b"snap\xffshot");
+    let snapshot = sv.snapshot();
+    assert_eq!(snapshot.len(), 9);
+    assert!(!snapshot.is_empty());
+    assert_eq!(snapshot.as_ptr(), sv.as_ptr());
+    assert_eq!(snapshot.as_bytes(), sv.as_bytes());
+    assert!(snapshot.to_str().is_err());
+    assert_eq!(snapshot.to_string_lossy(), "snap\u{fffd}shot");
+    assert_eq!(snapshot.to_string(), "snap\u{fffd}shot");
+    assert_eq!(snapshot.to_view(), sv);
+
+    let copy = snapshot;
+    let total: usize = (0..copy.len()).map(|i| copy.as_bytes()[i] as usize).sum();
+    assert_eq!(total, sv.as_bytes().iter().map(|&b| b as usize).sum());
+
+    let empty = cxx::CxxStringView::empty().snapshot();
+    assert!(empty.is_empty());
+    assert!(!empty.as_ptr().is_null());
+    assert_eq!(empty.to_str(), Ok(""));
+}
+
+#[test]
+fn test_split_str() {
+    fn split_str(input: &str, delimiter: &str) -> Vec<String> {
+        cxx::CxxStringView::new(input)
+            .split_str(delimiter)
+            .map(|part| part.to_string())
+            .collect()
+    }
+
+    assert_eq!(split_str("a::b::::c", "::"), ["a", "b", "", "c"]);
+    assert_eq!(split_str("aaaaa", "aa"), ["", "", "a"]);
+    assert_eq!(split_str("\r\nx\r\n", "\r\n"), ["", "x", ""]);
+    assert_eq!(split_str("abc", ""), ["", "a", "b", "c", ""]);
+    assert_eq!(split_str("", ""), ["", ""]);
+    assert_eq!(split_str("", ","), [""]);
+}
+
+#[test]
+fn test_eq_bool_and_number() {
+    for input in ["1", "true", "TRUE", "True"] {
+        assert_eq!(cxx::CxxStringView::new(input).eq_bool(), Some(true));
+    }
+    for input in ["0", "false", "FALSE", "fAlSe"] {
+        assert_eq!(cxx::CxxStringView::new(input).eq_bool(), Some(false));
+    }
+    for input in ["", "yes", "no", "2", " true", "true\n", "t", "01"] {
+        assert_eq!(cxx::CxxStringView::new(input).eq_bool(), None);
+    }
+    assert_eq!(cxx::CxxStringView::new(" 1 ").trim_ascii().eq_bool(), Some(true));
+
+    assert_eq!(cxx::CxxStringView::new("42").eq_number::<u32>(), Some(42));
+    assert_eq!(cxx::CxxStringView::new("-7").eq_number::<i64>(), Some(-7));
+    assert_eq!(cxx::CxxStringView::new("2.5").eq_number::<f64>(), Some(2.5));
+    assert_eq!(cxx::CxxStringView::new("-7").eq_number::<u32>(), None);
+    assert_eq!(cxx::CxxStringView::new(" 42").eq_number::<u32>(), None);
+    assert_eq!(cxx::CxxStringView::new("").eq_number::<u32>(), None);
+    assert_eq!(cxx::CxxStringView::new(b"4\xff").eq_number::<u32>(), None);
+}
+
+#[test]
+fn test_reborrow() {
+    fn consume(sv: cxx::CxxStringView) -> usize {
+        sv.len()
+    }
+
+    let sv = cxx::CxxStringView::new("reborrowed");
+    assert_eq!(consume(sv.reborrow()), 10);
+    assert_eq!(consume(sv.reborrow()), 10);
+    assert_eq!(sv.reborrow().as_ptr(), sv.as_ptr());
+    assert_eq!(&sv, "reborrowed");
+}
+
+#[test]
+fn test_match_ranges() {
+    let sv = cxx::CxxStringView::new("one fish, two fish, red fish");
+    let ranges: Vec<_> = sv.match_ranges("fish").collect();
+    assert_eq!(ranges, [4..8, 14..18, 24..28]);
+    for range in &ranges {
+        assert_eq!(range.len(), "fish".len());
+        assert_eq!(&sv.as_bytes()[range.clone()], b"fish");
+    }
+
+    let ranges: Vec<_> = cxx::CxxStringView::new("aaaaa").match_ranges("aa").collect();
+    assert_eq!(ranges, [0..2, 2..4]);
+    assert_eq!(sv.match_ranges("cat").count(), 0);
+
+    let ranges: Vec<_> = cxx::CxxStringView::new("ab").match_ranges("").collect();
+    assert_eq!(ranges, [0..0, 1..1, 2..2]);
+}
+
+#[test]
+fn test_hash_into() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let text = "the quick brown fox jumps over the lazy dog".repeat(10);
+    let sv = cxx::CxxStringView::new(&text);
+    let mut whole = DefaultHasher::new();
+    sv.hash(&mut whole);
+    let expected = whole.finish();
+
+    for &chunk_size in &[1, 3, 7, 64, text.len(), text.len() + 1] {
+        let mut chunked = DefaultHasher::new();
+        sv.hash_into(&mut chunked, chunk_size);
+        assert_eq!(chunked.finish(), expected, "chunk size {}", chunk_size);
+    }
+
+    let mut whole = DefaultHasher::new();
+    cxx::CxxStringView::empty().hash(&mut whole);
+    let mut chunked = DefaultHasher::new();
+    cxx::CxxStringView::empty().hash_into(&mut chunked, 8);
+    assert_eq!(chunked.finish(), whole.finish());
+}
+
+#[test]
+#[should_panic = "chunk size must be non-zero"]
+fn test_hash_into_zero_chunk_size() {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    cxx::CxxStringView::new("x").hash_into(&mut hasher, 0);
+}
+
+#[test]
+fn test_from_cxx_string() {
+    fn view_len<'a>(input: impl Into<cxx::CxxStringView<'a>>) -> usize {
+        input.into().len()
+    }
+
+    let_cxx_string!(s = "converted");
+    assert_eq!(view_len(&*s), 9);
+    let sv = cxx::CxxStringView::from(&*s);
+    assert_eq!(&sv, "converted");
+    assert_eq!(sv.as_ptr(), s.as_ptr());
+}
+
+#[test]
+fn test_split_once_str() {
+    let (key, value) = cxx::CxxStringView::new("a => b => c").split_once_str(" => ").unwrap();
+    assert_eq!(&key, "a");
+    assert_eq!(&value, "b => c");
+
+    let (key, value) = cxx::CxxStringView::new("Host: example.com").split_once_str(": ").unwrap();
+    assert_eq!(&key, "Host");
+    assert_eq!(&value, "example.com");
+
+    assert!(cxx::CxxStringView::new("no delimiter").split_once_str("=>").is_none());
+    assert!(cxx::CxxStringView::new("=").split_once_str("=>").is_none());
+
+    let (before, after) = cxx::CxxStringView::new("abc").split_once_str("").unwrap();
+    assert!(before.is_empty());
+    assert_eq!(&after, "abc");
+}
+
+#[test]
+fn test_to_json_string() {
+    let sv = cxx::CxxStringView::new("say \"hi\"\\n");
+    assert_eq!(sv.to_json_string(), r#""say \"hi\"\\n""#);
+
+    let sv = cxx::CxxStringView::new("tab\there\nnul\0bell\x07\r\x08\x0c\x1f");
+    assert_eq!(
+        sv.to_json_string(),
+        r#""tab\there\nnul\u0000bell\u0007\r\b\f\u001f""#,
+    );
+
+    let sv = cxx::CxxStringView::new(b"caf\xc3\xa9 \xff");
+    assert_eq!(sv.to_json_string(), "\"caf\u{e9} \u{fffd}\"");
+    assert_eq!(cxx::CxxStringView::empty().to_json_string(), r#""""#);
+}
+
+#[test]
+fn test_diff_str() {
+    let (common, left, right) = cxx::CxxStringView::new("same").diff_str("same");
+    assert_eq!(common, 4);
+    assert!(left.is_empty());
+    assert_eq!(right, "");
+
+    let (common, left, right) = cxx::CxxStringView::new("prefix").diff_str("prefix and more");
+    assert_eq!(common, 6);
+    assert!(left.is_empty());
+    assert_eq!(right, " and more");
+
+    let (common, left, right) = cxx::CxxStringView::new("hello world").diff_str("help");
+    assert_eq!(common, 3);
+    assert_eq!(&left, "lo world");
+    assert_eq!(right, "p");
+
+    let (common, left, right) = cxx::CxxStringView::new("caf\u{e9}").diff_str("caf\u{e8}");
+    assert_eq!(common, 3);
+    assert_eq!(&left, "\u{e9}");
+    assert_eq!(right, "\u{e8}");
+}
+
+#[test]
+fn test_to_cxx_string() {
+    let sv = cxx::CxxStringView::new(b"copied \x00\xff bytes");
+    let s = sv.to_cxx_string();
+    assert_eq!(s.as_bytes(), sv.as_bytes());
+    assert_ne!(s.as_ptr(), sv.as_ptr());
+    assert_eq!(*s, sv);
+    assert!(cxx::CxxStringView::empty().to_cxx_string().is_empty());
+}
+
+#[test]
+fn test_split_at_char_boundary() {
+    let sv = cxx::CxxStringView::new("caf\u{e9}!");
+    assert_eq!(sv.split_at_char_boundary(3), Some(("caf", "\u{e9}!")));
+    assert_eq!(sv.split_at_char_boundary(5), Some(("caf\u{e9}", "!")));
+    assert_eq!(sv.split_at_char_boundary(0), Some(("", "caf\u{e9}!")));
+    assert_eq!(sv.split_at_char_boundary(6), Some(("caf\u{e9}!", "")));
+    assert_eq!(sv.split_at_char_boundary(4), None);
+    assert_eq!(sv.split_at_char_boundary(7), None);
+
+    let invalid = cxx::CxxStringView::new(b"ab\xffcd");
+    assert_eq!(invalid.split_at_char_boundary(1), None);
+}
+
+#[test]
+fn test_suffix() {
+    let sv = cxx::CxxStringView::new("offset");
+    assert_eq!(sv.suffix(0), sv);
+    assert_eq!(&sv.suffix(3), "set");
+    assert!(sv.suffix(sv.len()).is_empty());
+}
+
+#[test]
+#[should_panic = "suffix index 7 is out of range for string view of length 6"]
+fn test_suffix_out_of_range() {
+    let _ = cxx::CxxStringView::new("offset").suffix(7);
+}
+
+#[test]
+fn test_cxx_string_writer() {
+    use std::fmt::Write;
+
+    let_cxx_string!(s = "");
+    let sv = cxx::CxxStringView::new(b"view \xff");
+    let mut writer = cxx::CxxStringWriter::new(s.as_mut());
+    write!(writer, "[{}]", sv).unwrap();
+    writeln!(writer, " and {:>4}", "lit").unwrap();
+    writer.into_inner().push_str("!");
+    assert_eq!(s.to_str().unwrap(), "[view \u{fffd}] and  lit\n!");
+}
+
+#[test]
+fn test_count_byte() {
+    let text = "a,b,,c\n".repeat(100);
+    let sv = cxx::CxxStringView::new(&text);
+    assert_eq!(sv.count_byte(b','), 300);
+    assert_eq!(sv.count_byte(b'\n'), 100);
+    assert_eq!(sv.count_byte(b'#'), 0);
+    for byte in 0..=u8::MAX {
+        let naive = text.bytes().filter(|&b| b == byte).count();
+        assert_eq!(sv.count_byte(byte), naive);
+    }
+    assert_eq!(cxx::CxxStringView::empty().count_byte(0), 0);
+}
+
+#[test]
+fn test_is_ascii_whitespace_only() {
+    assert!(cxx::CxxStringView::empty().is_ascii_whitespace_only());
+    assert!(cxx::CxxStringView::new("   ").is_ascii_whitespace_only());
+    assert!(cxx::CxxStringView::new(" \t\r\n\x0c").is_ascii_whitespace_only());
+    assert!(!cxx::CxxStringView::new("  x ").is_ascii_whitespace_only());
+    assert!(!cxx::CxxStringView::new("\x0b").is_ascii_whitespace_only());
+    assert!(!cxx::CxxStringView::new("\u{a0}").is_ascii_whitespace_only());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic = "string view has a null data pointer but a length of 5"]
+fn test_as_bytes_null_with_len() {
+    let sv = unsafe { cxx::CxxStringView::try_from_raw_parts(std::ptr::null(), 5) }.unwrap();
+    let _ = sv.as_bytes();
+}
+
+#[test]
+fn test_split_terminator_str() {
+    fn split_terminator_str(input: &str, delimiter: &str) -> Vec<String> {
+        cxx::CxxStringView::new(input)
+            .split_terminator_str(delimiter)
+            .map(|part| part.to_string())
+            .collect()
+    }
+
+    assert_eq!(split_terminator_str("a\r\nb\r\n", "\r\n"), ["a", "b"]);
+    assert_eq!(split_terminator_str("a\r\nb", "\r\n"), ["a", "b"]);
+    assert_eq!(split_terminator_str("a;;", ";"), ["a", ""]);
+    assert_eq!(split_terminator_str("\r\n", "\r\n"), [""]);
+    assert_eq!(split_terminator_str("", "\r\n"), Vec::<String>::new());
+    assert_eq!(split_terminator_str("abc", ""), ["", "a", "b", "c"]);
+    assert_eq!(split_terminator_str("", ""), [""]);
+}
+
+#[test]
+fn test_to_cow_str() {
+    use std::borrow::Cow;
+
+    let bytes = "caf\u{e9}".as_bytes();
+    let cow = cxx::CxxStringView::new(bytes).to_cow_str().unwrap();
+    assert!(matches!(cow, Cow::Borrowed(_)));
+    assert_eq!(cow, "caf\u{e9}");
+    assert_eq!(cow.as_ptr(), bytes.as_ptr());
+
+    let err = cxx::CxxStringView::new(b"ab\xffcd").to_cow_str().unwrap_err();
+    assert_eq!(err.valid_up_to(), 2);
+}
+
+#[test]
+fn test_hamming_distance() {
+    let sv = cxx::CxxStringView::new("karolin");
+    assert_eq!(sv.hamming_distance(&cxx::CxxStringView::new("karolin")), Some(0));
+    assert_eq!(sv.hamming_distance(&cxx::CxxStringView::new("karolan")), Some(1));
+    assert_eq!(sv.hamming_distance(&cxx::CxxStringView::new("kathrin")), Some(3));
+    assert_eq!(sv.hamming_distance(&cxx::CxxStringView::new("karoline")), None);
+    let empty = cxx::CxxStringView::empty();
+    assert_eq!(empty.hamming_distance(&cxx::CxxStringView::empty()), Some(0));
+}
+
+#[test]
+fn test_split_indices() {
+    let sv = cxx::CxxStringView::new("ab,,cd,");
+    let items: Vec<_> = sv
+        .split_indices(b',')
+        .map(|(offset, part)| (offset, part.to_string()))
+        .collect();
+    assert_eq!(
+        items,
+        [
+            (0, "ab".to_owned()),
+            (3, String::new()),
+            (4, "cd".to_owned()),
+            (7, String::new()),
+        ],
+    );
+    for (offset, part) in sv.split_indices(b',') {
+        assert_eq!(&sv.as_bytes()[offset..offset + part.len()], part.as_bytes());
+    }
+
+    let items: Vec<_> = cxx::CxxStringView::empty().split_indices(b',').collect();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].0, 0);
+}
+
+#[test]
+fn test_c_str_view() {
+    let sv = cxx::CxxStringView::new(b"name\0garbage\0\0");
+    assert_eq!(&sv.c_str_view(), "name");
+    assert_eq!(&sv.trim_end_nul(), "name\0garbage");
+    assert_eq!(&cxx::CxxStringView::new("no nul").c_str_view(), "no nul");
+    assert!(cxx::CxxStringView::new(b"\0leading").c_str_view().is_empty());
+    assert!(cxx::CxxStringView::empty().c_str_view().is_empty());
+}
+
+#[test]
+fn test_from_raw_parts_bounded() {
+    let buf = *b"bounded\0\0\0";
+    let ptr = buf.as_ptr();
+    let sv = unsafe { cxx::CxxStringView::from_raw_parts_bounded(ptr, 7, buf.len()) };
+    assert_eq!(&sv.unwrap(), "bounded");
+    let sv = unsafe { cxx::CxxStringView::from_raw_parts_bounded(ptr, buf.len(), buf.len()) };
+    assert_eq!(sv.unwrap().len(), buf.len());
+
+    let sv = unsafe { cxx::CxxStringView::from_raw_parts_bounded(ptr, 11, buf.len()) };
+    assert!(sv.is_none());
+    let sv = unsafe { cxx::CxxStringView::from_raw_parts_bounded(ptr, usize::MAX, usize::MAX) };
+    assert!(sv.is_none());
+}
+
+#[test]
+fn test_lossy_replacement_count() {
+    assert_eq!(cxx::CxxStringView::new("clean caf\u{e9}").lossy_replacement_count(), 0);
+    assert_eq!(cxx::CxxStringView::new("already \u{fffd}").lossy_replacement_count(), 0);
+    assert_eq!(cxx::CxxStringView::empty().lossy_replacement_count(), 0);
+
+    for input in [&b"a\xffb\xfe"[..], b"\xe2\x82", b"\xf0\x9f\x98x\x80\x80"] {
+        let sv = cxx::CxxStringView::new(input);
+        let lossy = String::from_utf8_lossy(input);
+        let expected = lossy.matches('\u{fffd}').count();
+        assert_eq!(sv.lossy_replacement_count(), expected, "{:?}", input);
+    }
+    assert_eq!(cxx::CxxStringView::new(b"a\xffb\xfe").lossy_replacement_count(), 2);
+}
+
+#[test]
+fn test_view_concatenator() {
+    let mut concat = cxx::ViewConcatenator::new();
+    assert_eq!(concat.finish(), b"");
+
+    concat.push(&cxx::CxxStringView::new("a much longer first record, "));
+    concat.push(&cxx::CxxStringView::new("in two parts"));
+    assert_eq!(concat.finish(), &b"a much longer first record, in two parts"[..]);
+    let capacity = concat.capacity();
+    assert!(capacity >= 40);
+
+    for i in 0..10 {
+        let number = i.to_string();
+        concat.push(&cxx::CxxStringView::new("item "));
+        concat.push(&cxx::CxxStringView::new(&number));
+        assert_eq!(concat.finish(), format!("item {}", i).as_bytes());
+        assert_eq!(concat.finish(), format!("item {}", i).as_bytes());
+        assert_eq!(concat.capacity(), capacity);
+    }
+}
+
+#[test]
+fn test_split_after_nth() {
+    let sv = cxx::CxxStringView::new("a,b,c,d");
+    let (fields, rest) = sv.split_after_nth(b',', 2).unwrap();
+    assert_eq!(&fields, "a,b");
+    assert_eq!(&rest, "c,d");
+
+    let (fields, rest) = sv.split_after_nth(b',', 3).unwrap();
+    assert_eq!(&fields, "a,b,c");
+    assert_eq!(&rest, "d");
+
+    let (fields, rest) = cxx::CxxStringView::new("a,b,").split_after_nth(b',', 2).unwrap();
+    assert_eq!(&fields, "a,b");
+    assert!(rest.is_empty());
+
+    let (fields, rest) = cxx::CxxStringView::new(",,x").split_after_nth(b',', 2).unwrap();
+    assert_eq!(&fields, ",");
+    assert_eq!(&rest, "x");
+
+    assert!(sv.split_after_nth(b',', 4).is_none());
+    assert!(sv.split_after_nth(b';', 1).is_none());
+
+    let (fields, rest) = sv.split_after_nth(b',', 0).unwrap();
+    assert!(fields.is_empty());
+    assert_eq!(rest, sv);
+}
+
+#[test]
+fn test_to_owned_buf() {
+    let owned = {
+        let source = String::from("short-lived source");
+        let sv = cxx::CxxStringView::new(&source);
+        let owned = sv.to_owned_buf();
+        assert!(owned == sv);
+        owned
+    };
+    assert_eq!(&owned.to_string_view(), "short-lived source");
+    assert!(cxx::CxxStringView::empty().to_owned_buf() == cxx::CxxStringView::empty());
+}
+
+#[test]
+fn test_is_sorted_bytes() {
+    assert!(cxx::CxxStringView::new("aabcxyz").is_sorted_bytes());
+    assert!(cxx::CxxStringView::new(b"\x00\x7f\x80\xff").is_sorted_bytes());
+    assert!(!cxx::CxxStringView::new("abdc").is_sorted_bytes());
+    assert!(!cxx::CxxStringView::new(b"\xff\x00").is_sorted_bytes());
+    assert!(cxx::CxxStringView::new("z").is_sorted_bytes());
+    assert!(cxx::CxxStringView::empty().is_sorted_bytes());
+}
+
+#[test]
+fn test_as_i8_slice() {
+    let bytes = [0u8, 1, 0x7f, 0x80, 0xfe, 0xff];
+    let sv = cxx::CxxStringView::new(&bytes);
+    let signed = sv.as_i8_slice();
+    assert_eq!(signed, [0, 1, 127, -128, -2, -1]);
+    assert_eq!(signed.as_ptr().cast::<u8>(), sv.as_ptr());
+    let round_trip: Vec<u8> = signed.iter().map(|&b| b as u8).collect();
+    assert_eq!(round_trip, bytes);
+    assert!(cxx::CxxStringView::empty().as_i8_slice().is_empty());
+}
+
+#[test]
+fn test_starts_and_ends_with_any() {
+    let sv = cxx::CxxStringView::new("archive.tar.gz");
+    assert_eq!(sv.ends_with_any(&[".tar.gz", ".gz"]), Some(0));
+    assert_eq!(sv.ends_with_any(&[".gz", ".tar.gz"]), Some(0));
+    assert_eq!(sv.ends_with_any(&[".zip", ".gz", ".tar.gz"]), Some(1));
+    assert_eq!(sv.ends_with_any(&[".zip", ".tar"]), None);
+    assert_eq!(sv.ends_with_any::<&str>(&[]), None);
+
+    assert_eq!(sv.starts_with_any(&["arch", "archive"]), Some(0));
+    assert_eq!(sv.starts_with_any(&["archive.", "arch"]), Some(0));
+    assert_eq!(sv.starts_with_any(&["zip", "archive"]), Some(1));
+    assert_eq!(sv.starts_with_any(&["tar"]), None);
+    assert_eq!(cxx::CxxStringView::empty().ends_with_any(&[""]), Some(0));
+}
+
+#[test]
+fn test_get_or() {
+    let sv = cxx::CxxStringView::new("abc");
+    assert_eq!(sv.get_or(0, b'?'), b'a');
+    assert_eq!(sv.get_or(2, b'?'), b'c');
+    assert_eq!(sv.get_or(3, b'?'), b'?');
+    assert_eq!(sv.get_or(usize::MAX, 0), 0);
+    assert_eq!(cxx::CxxStringView::empty().get_or(0, b'-'), b'-');
+}
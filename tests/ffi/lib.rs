@@ -143,6 +143,9 @@ pub mod ffi {
         fn c_take_rust_string(s: String);
         // #[cfg(any(feature="c++17", feature="c++20"))]
         fn c_round_trip_string_view(sv: CxxStringView) -> CxxStringView;
+        fn c_sum_string_view_lengths(views: &[CxxStringView]) -> usize;
+        #[cxx_name = "c_round_trip_string_view"]
+        fn c_round_trip_string_view_alias(sv: StringView) -> StringView;
         fn c_take_unique_ptr_string(s: UniquePtr<CxxString>);
         fn c_take_unique_ptr_vector_u8(v: UniquePtr<CxxVector<u8>>);
         fn c_take_unique_ptr_vector_f64(v: UniquePtr<CxxVector<f64>>);
@@ -192,6 +195,7 @@ pub mod ffi {
         fn c_try_return_rust_vec() -> Result<Vec<u8>>;
         fn c_try_return_rust_vec_string() -> Result<Vec<String>>;
         fn c_try_return_ref_rust_vec(c: &C) -> Result<&Vec<u8>>;
+        fn c_fail_return_string_view(sv: CxxStringView) -> Result<CxxStringView>;
 
         fn get(self: &C) -> usize;
         fn set(self: Pin<&mut C>, n: usize) -> usize;
@@ -200,6 +204,8 @@ pub mod ffi {
         fn getMut(self: Pin<&mut C>) -> &mut usize;
         fn set_succeed(self: Pin<&mut C>, n: usize) -> Result<usize>;
         fn get_fail(self: Pin<&mut C>) -> Result<usize>;
+        #[string_view]
+        fn get_v_view(self: &C) -> &str;
         fn c_method_on_shared(self: &Shared) -> usize;
         fn c_method_ref_on_shared(self: &Shared) -> &usize;
         fn c_method_mut_on_shared(self: &mut Shared) -> &mut usize;
@@ -244,6 +250,13 @@ pub mod ffi {
         fn nonconst_member(self: Pin<&mut Borrow>);
     }
 
+    unsafe extern "C++" {
+        type Record;
+
+        fn c_return_unique_ptr_record(name: &str) -> UniquePtr<Record>;
+        fn name(&self) -> CxxStringView;
+    }
+
     #[repr(u32)]
     #[derive(Hash)]
     enum COwnedEnum {
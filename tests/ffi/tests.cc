@@ -55,6 +55,23 @@ const std::vector<uint8_t> &C::get_v() const { return this->v; }
 
 std::vector<uint8_t> &C::get_v() { return this->v; }
 
+#if defined(__cpp_lib_string_view)
+std::string_view C::get_v_view() const {
+  return std::string_view(reinterpret_cast<const char *>(this->v.data()),
+                          this->v.size());
+}
+#endif  // defined(__cpp_lib_string_view)
+
+Record::Record(std::string name) : record_name(std::move(name)) {}
+
+#if defined(__cpp_lib_string_view)
+std::string_view Record::name() const { return this->record_name; }
+#endif  // defined(__cpp_lib_string_view)
+
+std::unique_ptr<Record> c_return_unique_ptr_record(rust::Str name) {
+  return std::unique_ptr<Record>(new Record(std::string(name)));
+}
+
 size_t c_return_primitive() { return 2020; }
 
 Shared c_return_shared() { return Shared{2020}; }
@@ -346,6 +363,15 @@ std::string_view c_round_trip_string_view(std::string_view sv) {
   }
   return "Hello from C++!";
 }
+
+std::size_t
+c_sum_string_view_lengths(rust::Slice<const std::string_view> views) {
+  std::size_t total = 0;
+  for (auto view : views) {
+    total += view.length();
+  }
+  return total;
+}
 #endif  // defined(__cpp_lib_string_view)
 
 void c_take_unique_ptr_string(std::unique_ptr<std::string> s) {
@@ -605,6 +631,12 @@ const rust::Vec<uint8_t> &c_try_return_ref_rust_vec(const C &c) {
   throw std::runtime_error("unimplemented");
 }
 
+#if defined(__cpp_lib_string_view)
+std::string_view c_fail_return_string_view(std::string_view sv) {
+  throw std::runtime_error("no view of " + std::string(sv));
+}
+#endif  // defined(__cpp_lib_string_view)
+
 size_t c_get_use_count(const std::weak_ptr<C> &weak) noexcept {
   return weak.use_count();
 }
@@ -54,6 +54,9 @@ public:
   size_t get_fail();
   const std::vector<uint8_t> &get_v() const;
   std::vector<uint8_t> &get_v();
+#if defined(__cpp_lib_string_view)
+  std::string_view get_v_view() const;
+#endif  // defined(__cpp_lib_string_view)
   rust::String cOverloadedMethod(int32_t x) const;
   rust::String cOverloadedMethod(rust::Str x) const;
 
@@ -62,6 +65,19 @@ private:
   std::vector<uint8_t> v;
 };
 
+class Record {
+public:
+  Record(std::string name);
+#if defined(__cpp_lib_string_view)
+  std::string_view name() const;
+#endif  // defined(__cpp_lib_string_view)
+
+private:
+  std::string record_name;
+};
+
+std::unique_ptr<Record> c_return_unique_ptr_record(rust::Str name);
+
 struct D {
   uint64_t d;
   void c_take_trivial_ref_method() const;
@@ -146,6 +162,8 @@ void c_take_slice_r_sort(rust::Slice<R> s);
 void c_take_rust_string(rust::String s);
 #if defined(__cpp_lib_string_view)
 std::string_view c_round_trip_string_view(std::string_view sv);
+std::size_t
+c_sum_string_view_lengths(rust::Slice<const std::string_view> views);
 #endif  // defined(__cpp_lib_string_view)
 void c_take_unique_ptr_string(std::unique_ptr<std::string> s);
 void c_take_unique_ptr_vector_u8(std::unique_ptr<std::vector<uint8_t>> v);
@@ -193,6 +211,9 @@ std::unique_ptr<std::string> c_try_return_unique_ptr_string();
 rust::Vec<uint8_t> c_try_return_rust_vec();
 rust::Vec<rust::String> c_try_return_rust_vec_string();
 const rust::Vec<uint8_t> &c_try_return_ref_rust_vec(const C &c);
+#if defined(__cpp_lib_string_view)
+std::string_view c_fail_return_string_view(std::string_view sv);
+#endif  // defined(__cpp_lib_string_view)
 
 size_t c_get_use_count(const std::weak_ptr<C> &weak) noexcept;
 
@@ -386,4 +386,56 @@ fn test_round_trip_string_view() {
     let sv = cxx::CxxStringView::new(&hello);
     let from_cxx = ffi::c_round_trip_string_view(sv);
     assert_eq!(&from_cxx, "Hello from C++!");
+}
+
+#[cfg(any(feature="c++17", feature="c++20"))]
+#[test]
+fn test_exception_what_view() {
+    let sv = cxx::CxxStringView::new("input");
+    let err = ffi::c_fail_return_string_view(sv).unwrap_err();
+    let what: cxx::CxxStringView = err.what_view();
+    assert_eq!(&what, "no view of input");
+    assert_eq!(what.as_ptr(), err.what().as_ptr());
+}
+
+#[cfg(any(feature="c++17", feature="c++20"))]
+#[test]
+fn test_string_view_alias() {
+    let sv: cxx::StringView = cxx::StringView::new("Hello from Rust!");
+    let from_cxx: cxx::StringView = ffi::c_round_trip_string_view_alias(sv);
+    assert_eq!(&from_cxx, "Hello from C++!");
+}
+
+#[cfg(any(feature="c++17", feature="c++20"))]
+#[test]
+fn test_string_view_attribute() {
+    let mut c = ffi::c_return_unique_ptr();
+    let mut v = ffi::c_return_mut_vector(c.pin_mut());
+    for &b in b"view" {
+        v.as_mut().push(b);
+    }
+    let view: cxx::CxxStringView = c.get_v_view();
+    assert_eq!(&view, "view");
+}
+
+#[cfg(any(feature="c++17", feature="c++20"))]
+#[test]
+fn test_unique_ptr_string_view_accessor() {
+    let record = ffi::c_return_unique_ptr_record("a record name that is too long for SSO");
+    let name: cxx::CxxStringView = record.name();
+    assert_eq!(&name, "a record name that is too long for SSO");
+    assert_eq!(name, record.as_ref().unwrap().name());
+}
+
+#[cfg(any(feature="c++17", feature="c++20"))]
+#[test]
+fn test_slice_of_string_view() {
+    let first = String::from("first");
+    let views = [
+        cxx::CxxStringView::new(&first),
+        cxx::CxxStringView::empty(),
+        cxx::CxxStringView::new("second"),
+    ];
+    assert_eq!(ffi::c_sum_string_view_lengths(&views), 11);
+    assert_eq!(ffi::c_sum_string_view_lengths(&[]), 0);
 }
\ No newline at end of file
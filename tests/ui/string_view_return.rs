@@ -0,0 +1,20 @@
+#[cxx::bridge]
+mod ffi {
+    unsafe extern "C++" {
+        type C;
+
+        #[string_view]
+        fn f(self: &C) -> &[u8];
+    }
+
+    extern "Rust" {
+        #[string_view]
+        fn g(s: &str) -> &str;
+    }
+}
+
+fn g(s: &str) -> &str {
+    s
+}
+
+fn main() {}